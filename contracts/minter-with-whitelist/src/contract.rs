@@ -2,24 +2,36 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     has_coins, to_binary, Addr, BalanceResponse, BankMsg, BankQuery, Binary, Coin, CosmosMsg, Deps,
-    DepsMut, Env, MessageInfo, Reply, ReplyOn, Response, StdResult, SubMsg, Uint128, WasmMsg,
+    DepsMut, Empty, Env, MessageInfo, Order, Reply, ReplyOn, Response, StdError, StdResult,
+    Storage, SubMsg, Uint128, WasmMsg,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, query_contract_info, set_contract_version};
 use cw20::MinterResponse;
 use cw20_base::msg::{ExecuteMsg as Cw20ExecuteMsg, InstantiateMsg as Cw20InstantiateMsg};
 use cw_utils::parse_reply_instantiate_data;
+use ownership::{
+    accept_ownership, get_ownership, initialize_owner, is_owner, propose_new_owner,
+    renounce_ownership, OwnershipError,
+};
 use price_feed::msg::{QueryMsg as PriceFeedQueryMsg, RoundDataResponse};
+use semver::Version;
 
 use crate::error::ContractError;
-use crate::msg::{ExchangingInfoResponse, ExecuteMsg, InstantiateMsg, QueryMsg, ReceiverResponse};
+use crate::migrations::migrate_from_v0_1;
+use crate::msg::{
+    ExchangingInfoResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ReceiverResponse,
+};
 use crate::state::{
-    Asset, AssetType, Config, ExchangingInfo, Requirement, RequirementAssets, EXCHANGING_INFO,
-    REQUIREMENT_ASSETS,
+    Asset, AssetType, Config, ExchangingInfo, Operation, Requirement, RequirementAssets,
+    EXCHANGING_INFO, PAUSED_OPERATIONS, REQUIREMENT_ASSETS,
 };
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:minter-with-whitelist";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+// the name of the price feed contract, its v0.1 does not support the circuit breaker
+// and the heartbeat queries
+const PRICE_FEED_CONTRACT_NAME: &str = "crates.io:price-feed";
 
 /// Handling contract instantiation
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -31,10 +43,13 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    // the sender is the first owner
+    initialize_owner(deps.storage, deps.api, info.sender.as_str())?;
+
     // init config
     let config = Config {
-        owner: info.sender.clone(),
         receiver_name: msg.receiver_name.clone(),
+        guardian: None,
     };
     crate::state::CONFIG.save(deps.storage, &config)?;
 
@@ -95,16 +110,60 @@ pub fn execute(
             expected_received,
         } => execute_exchange(deps, env, info, amount, expected_received),
         ExecuteMsg::Withdraw {} => execute_withdraw(deps, env, info),
+        ExecuteMsg::UpdateGuardian { guardian } => {
+            execute_update_guardian(deps, env, info, guardian)
+        }
+        ExecuteMsg::Pause { operation } => execute_pause(deps, env, info, operation),
+        ExecuteMsg::Unpause { operation } => execute_unpause(deps, env, info, operation),
+        ExecuteMsg::ProposeNewOwner { new_owner, expiry } => {
+            Ok(propose_new_owner(deps, &env, &info, new_owner, expiry)?)
+        }
+        ExecuteMsg::AcceptOwnership {} => Ok(accept_ownership(deps, &env, &info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(renounce_ownership(deps, &env, &info)?),
     }
 }
 
+/// Handling contract migration
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // only an older or the same version of the minter can be migrated
+    let previous = get_contract_version(deps.storage)?;
+    if previous.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigration {
+            contract: previous.contract,
+            version: previous.version,
+        });
+    }
+    let previous_version: Version = previous.version.parse()?;
+    if previous_version > CONTRACT_VERSION.parse::<Version>()? {
+        return Err(ContractError::CannotDowngrade {
+            version: previous.version,
+        });
+    }
+
+    // transform the state of each previous version in order
+    if previous_version < Version::new(0, 2, 0) {
+        migrate_from_v0_1(deps.branch())?;
+    }
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "migrate"),
+        ("previous_version", previous.version.as_str()),
+        ("version", CONTRACT_VERSION),
+    ]))
+}
+
 /// Handling contract query
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
+        QueryMsg::Ownership {} => to_binary(&get_ownership(deps.storage)?),
         QueryMsg::Owner {} => to_binary(&query_owner(deps)?),
         QueryMsg::Receiver {} => to_binary(&query_receiver(deps)?),
         QueryMsg::ExchangingInfo {} => to_binary(&query_exchanging_info(deps)?),
+        QueryMsg::Guardian {} => to_binary(&crate::state::CONFIG.load(deps.storage)?.guardian),
+        QueryMsg::PausedOperations {} => to_binary(&query_paused_operations(deps)?),
     }
 }
 
@@ -134,11 +193,14 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
 
 pub fn execute_exchange(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     amount: Uint128,
     expected_received: Uint128,
 ) -> Result<Response, ContractError> {
+    // the exchange can be paused by the guardian in an emergency
+    assert_not_paused(deps.storage, Operation::Exchange)?;
+
     // the funds must have enough offer token
     let exchanging_info = EXCHANGING_INFO.load(deps.storage)?;
     let offer_token = Coin {
@@ -153,13 +215,37 @@ pub fn execute_exchange(
 
     // query last round data from price feed
     // query the owner of the nft
-    let lastest_round_data: RoundDataResponse = deps
-        .querier
-        .query_wasm_smart(
-            exchanging_info.price_feed,
-            &PriceFeedQueryMsg::LastestRoundData {},
-        )
-        .unwrap();
+    let lastest_round_data: RoundDataResponse = deps.querier.query_wasm_smart(
+        exchanging_info.price_feed.clone(),
+        &PriceFeedQueryMsg::LastestRoundData {},
+    )?;
+
+    // the exchanges are halted while the circuit breaker of the price feed is open,
+    // a price feed of v0.1 does not support the query, so it has no circuit breaker
+    let legacy_price_feed = is_legacy_price_feed(deps.as_ref(), &exchanging_info.price_feed)?;
+    if !legacy_price_feed {
+        let circuit_open: bool = deps.querier.query_wasm_smart(
+            exchanging_info.price_feed.clone(),
+            &PriceFeedQueryMsg::CircuitOpen {},
+        )?;
+        if circuit_open {
+            return Err(ContractError::CircuitOpen {});
+        }
+    }
+
+    // the price must be updated within the heartbeat of the price feed,
+    // a price feed of v0.1 does not support the query, so it has no heartbeat
+    let heartbeat: Option<u64> = if legacy_price_feed {
+        None
+    } else {
+        deps.querier
+            .query_wasm_smart(exchanging_info.price_feed, &PriceFeedQueryMsg::Heartbeat {})?
+    };
+    if let Some(heartbeat) = heartbeat {
+        if lastest_round_data.is_stale(heartbeat, env.block.time) {
+            return Err(ContractError::StalePrice {});
+        }
+    }
 
     // calculate the amount of stable token to be minted
     let stable_token_amount = amount * lastest_round_data.answer;
@@ -191,8 +277,7 @@ pub fn execute_withdraw(
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
     // only owner can withdraw
-    let config = crate::state::CONFIG.load(deps.storage)?;
-    if info.sender != config.owner {
+    if !is_owner(deps.storage, &info.sender)? {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -221,9 +306,99 @@ pub fn execute_withdraw(
     ]))
 }
 
-pub fn query_owner(deps: Deps) -> StdResult<String> {
+pub fn execute_update_guardian(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    guardian: Option<String>,
+) -> Result<Response, ContractError> {
+    // only owner can update the guardian
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // update the guardian, none removes the guardian
+    let mut config = crate::state::CONFIG.load(deps.storage)?;
+    config.guardian = guardian
+        .map(|guardian| deps.api.addr_validate(&guardian))
+        .transpose()?;
+    crate::state::CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "update_guardian"),
+        (
+            "guardian",
+            &config
+                .guardian
+                .map_or("none".to_string(), |guardian| guardian.to_string()),
+        ),
+    ]))
+}
+
+pub fn execute_pause(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    operation: Operation,
+) -> Result<Response, ContractError> {
+    // only owner or guardian can pause an operation
     let config = crate::state::CONFIG.load(deps.storage)?;
-    Ok(config.owner.to_string())
+    if !is_owner(deps.storage, &info.sender)? && Some(&info.sender) != config.guardian.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if PAUSED_OPERATIONS.has(deps.storage, operation.as_str()) {
+        return Err(ContractError::AlreadyPaused {});
+    }
+    PAUSED_OPERATIONS.save(deps.storage, operation.as_str(), &Empty {})?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "pause"),
+        ("operation", operation.as_str()),
+        ("sender", info.sender.as_str()),
+    ]))
+}
+
+pub fn execute_unpause(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    operation: Operation,
+) -> Result<Response, ContractError> {
+    // only owner can resume a paused operation
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !PAUSED_OPERATIONS.has(deps.storage, operation.as_str()) {
+        return Err(ContractError::NotPaused {});
+    }
+    PAUSED_OPERATIONS.remove(deps.storage, operation.as_str());
+
+    Ok(Response::new().add_attributes([("method", "unpause"), ("operation", operation.as_str())]))
+}
+
+// the price feed of v0.1 is recognized by its contract version, any other price feed
+// which fails the queries halts the exchanges
+fn is_legacy_price_feed(deps: Deps, price_feed: &Addr) -> Result<bool, ContractError> {
+    let version = query_contract_info(&deps.querier, price_feed)?;
+    Ok(version.contract == PRICE_FEED_CONTRACT_NAME
+        && version.version.parse::<Version>()? < Version::new(0, 2, 0))
+}
+
+fn assert_not_paused(storage: &dyn Storage, operation: Operation) -> Result<(), ContractError> {
+    if PAUSED_OPERATIONS.has(storage, operation.as_str()) {
+        return Err(ContractError::Paused {});
+    }
+    Ok(())
+}
+
+// the renounced or pending ownership is found by the Ownership query
+pub fn query_owner(deps: Deps) -> StdResult<String> {
+    get_ownership(deps.storage)?
+        .owner
+        .map(|owner| owner.to_string())
+        .ok_or_else(|| StdError::generic_err(OwnershipError::NoOwner {}.to_string()))
 }
 
 pub fn query_receiver(deps: Deps) -> StdResult<ReceiverResponse> {
@@ -235,6 +410,12 @@ pub fn query_receiver(deps: Deps) -> StdResult<ReceiverResponse> {
     })
 }
 
+pub fn query_paused_operations(deps: Deps) -> StdResult<Vec<String>> {
+    PAUSED_OPERATIONS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
 pub fn query_exchanging_info(deps: Deps) -> StdResult<ExchangingInfoResponse> {
     let exchanging_info = EXCHANGING_INFO.load(deps.storage)?;
     Ok(ExchangingInfoResponse {
@@ -251,8 +432,7 @@ pub fn add_requirement_assets(
     assets: Vec<Asset>,
 ) -> Result<Response, ContractError> {
     // only owner can add requirement assets
-    let config = crate::state::CONFIG.load(deps.storage)?;
-    if info.sender != config.owner {
+    if !is_owner(deps.storage, &info.sender)? {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -274,8 +454,7 @@ pub fn remove_asset_types(
     asset_types: Vec<AssetType>,
 ) -> Result<Response, ContractError> {
     // only owner can remove asset types
-    let config = crate::state::CONFIG.load(deps.storage)?;
-    if info.sender != config.owner {
+    if !is_owner(deps.storage, &info.sender)? {
         return Err(ContractError::Unauthorized {});
     }
 
@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use ownership::OwnershipError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,6 +7,12 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("{0}")]
+    SemVer(#[from] semver::Error),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -15,8 +22,28 @@ pub enum ContractError {
     #[error("Expected received not matched")]
     ExpectedReceivedNotMatched {},
 
+    #[error("Stale price")]
+    StalePrice {},
+
+    #[error("Circuit open")]
+    CircuitOpen {},
+
     #[error("Custom Error val: {val:?}")]
     CustomError { val: String },
     // Add any other custom errors you like here.
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
+    #[error("Cannot migrate from {contract} {version}")]
+    InvalidMigration { contract: String, version: String },
+
+    #[error("Cannot migrate from the newer version {version}")]
+    CannotDowngrade { version: String },
+
+    #[error("Operation is paused")]
+    Paused {},
+
+    #[error("Operation is already paused")]
+    AlreadyPaused {},
+
+    #[error("Operation is not paused")]
+    NotPaused {},
 }
@@ -0,0 +1,35 @@
+use cosmwasm_std::{DepsMut, StdResult};
+use ownership::initialize_owner;
+
+use crate::state::{Config, CONFIG};
+
+// the state layout of v0.1, which is replaced by the current one
+pub mod v0_1 {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::Addr;
+    use cw_storage_plus::Item;
+
+    #[cw_serde]
+    pub struct Config {
+        pub owner: Addr,
+        pub receiver_name: String,
+    }
+
+    pub const CONFIG: Item<Config> = Item::new("config");
+}
+
+// migrate the state of v0.1 to the current layout
+pub fn migrate_from_v0_1(deps: DepsMut) -> StdResult<()> {
+    // the owner moves to the ownership
+    let config = v0_1::CONFIG.load(deps.storage)?;
+    initialize_owner(deps.storage, deps.api, config.owner.as_str())?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            receiver_name: config.receiver_name,
+            guardian: None,
+        },
+    )?;
+
+    Ok(())
+}
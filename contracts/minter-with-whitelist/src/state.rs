@@ -1,11 +1,26 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Empty};
+use cw_storage_plus::{Item, Map};
 
 #[cw_serde]
 pub struct Config {
-    pub owner: Addr,
     pub receiver_name: String,
+    // the guardian can pause the operations in an emergency, like the owner
+    pub guardian: Option<Addr>,
+}
+
+// the operations which can be paused independently
+#[cw_serde]
+pub enum Operation {
+    Exchange,
+}
+
+impl Operation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operation::Exchange => "exchange",
+        }
+    }
 }
 
 /// The information of exchanging
@@ -54,33 +69,27 @@ impl RequirementAssets {
                 (
                     AssetType::NativeToken { denom },
                     AssetType::NativeToken { denom: other_denom },
-                ) => {
-                    if denom == other_denom {
-                        a.amount = asset.amount;
-                        return;
-                    }
+                ) if denom == other_denom => {
+                    a.amount = asset.amount;
+                    return;
                 }
                 (
                     AssetType::CW20Token { cw20_address },
                     AssetType::CW20Token {
                         cw20_address: other_cw20_address,
                     },
-                ) => {
-                    if cw20_address == other_cw20_address {
-                        a.amount = asset.amount;
-                        return;
-                    }
+                ) if cw20_address == other_cw20_address => {
+                    a.amount = asset.amount;
+                    return;
                 }
                 (
                     AssetType::CW721Token { cw721_address },
                     AssetType::CW721Token {
                         cw721_address: other_cw721_address,
                     },
-                ) => {
-                    if cw721_address == other_cw721_address {
-                        a.amount = asset.amount;
-                        return;
-                    }
+                ) if cw721_address == other_cw721_address => {
+                    a.amount = asset.amount;
+                    return;
                 }
                 _ => {}
             }
@@ -112,4 +121,6 @@ impl RequirementAssets {
 
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const EXCHANGING_INFO: Item<ExchangingInfo> = Item::new("exchanging_info");
+// the paused operations, keyed by the operation name
+pub const PAUSED_OPERATIONS: Map<&str, Empty> = Map::new("paused_operations");
 pub const REQUIREMENT_ASSETS: Item<RequirementAssets> = Item::new("requirement_assets");
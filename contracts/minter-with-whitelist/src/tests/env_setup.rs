@@ -1,13 +1,19 @@
 #[cfg(test)]
 pub mod env {
-    use cosmwasm_std::{Addr, Coin, Empty, Uint128};
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{
+        to_binary, Addr, Binary, Coin, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+        Uint128,
+    };
 
     use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
 
     use crate::contract::{
-        execute as MinterExecute, instantiate as MinterInstantiate, query as MinterQuery,
-        reply as MinterReply,
+        execute as MinterExecute, instantiate as MinterInstantiate, migrate as MinterMigrate,
+        query as MinterQuery, reply as MinterReply,
     };
+    use crate::migrations::v0_1;
+    use crate::state::{ExchangingInfo, EXCHANGING_INFO};
 
     use price_feed::contract::{
         execute as PriceFeedExecute, instantiate as PriceFeedInstantiate, query as PriceFeedQuery,
@@ -20,13 +26,14 @@ pub mod env {
     use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
 
     use crate::msg::InstantiateMsg as MinterInstantiateMsg;
-    use price_feed::msg::InstantiateMsg as PriceFeedInstantiateMsg;
+    use price_feed::msg::{InstantiateMsg as PriceFeedInstantiateMsg, RoundDataResponse};
 
     pub const ADMIN: &str = "aura1000000000000000000000000000000000admin";
     pub const USER1: &str = "aura1000000000000000000000000000000000user1";
     pub const AURA: &str = "aura10000000000000000000000000000000000aura";
     pub const CONTROLLER: &str = "aura10000000000000000000000000000controller";
     pub const CONTROLLER_FAKE: &str = "aura10000000000000000000000000000fake";
+    pub const GUARDIAN: &str = "aura100000000000000000000000000000guardian";
 
     pub const NATIVE_DENOM: &str = "uaura";
     pub const NATIVE_BALANCE: u128 = 1_000_000_000_000u128;
@@ -36,7 +43,6 @@ pub mod env {
 
     pub struct ContractInfo {
         pub contract_addr: String,
-        pub contract_code_id: u64,
     }
 
     // create app instance and init balance of NATIVE token for admin
@@ -65,10 +71,144 @@ pub mod env {
     // create minter contract
     pub fn minter_contract_template() -> Box<dyn Contract<Empty>> {
         let contract = ContractWrapper::new(MinterExecute, MinterInstantiate, MinterQuery)
-            .with_reply(MinterReply);
+            .with_reply(MinterReply)
+            .with_migrate(MinterMigrate);
         Box::new(contract)
     }
 
+    // the minter of v0.1 writes its config in the old layout and stores the given version
+    fn legacy_instantiate(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        version: String,
+    ) -> StdResult<Response> {
+        cw2::set_contract_version(deps.storage, "crates.io:minter-with-whitelist", version)?;
+        v0_1::CONFIG.save(
+            deps.storage,
+            &v0_1::Config {
+                owner: info.sender,
+                receiver_name: "receiver".to_string(),
+            },
+        )?;
+        EXCHANGING_INFO.save(
+            deps.storage,
+            &ExchangingInfo {
+                accepted_denom: NATIVE_DENOM.to_string(),
+                receiver_address: Addr::unchecked(AURA),
+                token_address: Addr::unchecked(AURA),
+                price_feed: Addr::unchecked(CONTROLLER),
+            },
+        )?;
+        Ok(Response::new())
+    }
+
+    // instantiate a legacy minter which stores the given version and can be migrated
+    // by the admin, returns its address and the code id of the current minter
+    pub fn instantiate_legacy_minter(app: &mut App, version: &str) -> (Addr, u64) {
+        let legacy_contract_code_id = app.store_code(Box::new(ContractWrapper::new(
+            MinterExecute,
+            legacy_instantiate,
+            MinterQuery,
+        )));
+        let minter_contract_code_id = app.store_code(minter_contract_template());
+        let legacy_contract_addr = app
+            .instantiate_contract(
+                legacy_contract_code_id,
+                Addr::unchecked(ADMIN),
+                &version,
+                &[],
+                "test instantiate contract",
+                Some(ADMIN.to_string()),
+            )
+            .unwrap();
+        (legacy_contract_addr, minter_contract_code_id)
+    }
+
+    // the price feed of v0.1 only answers the latest round data,
+    // it does not support the heartbeat and the circuit breaker queries,
+    // its contract version is set by the test to tell it apart from a broken price feed
+    #[cw_serde]
+    pub enum LegacyPriceFeedQueryMsg {
+        LastestRoundData {},
+    }
+
+    fn legacy_price_feed_instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        version: String,
+    ) -> StdResult<Response> {
+        cw2::set_contract_version(deps.storage, "crates.io:price-feed", version)?;
+        Ok(Response::new())
+    }
+
+    fn legacy_price_feed_query(
+        _deps: Deps,
+        env: Env,
+        msg: LegacyPriceFeedQueryMsg,
+    ) -> StdResult<Binary> {
+        match msg {
+            LegacyPriceFeedQueryMsg::LastestRoundData {} => to_binary(&RoundDataResponse {
+                round_id: env.block.height,
+                answer: Uint128::from(10000000u64),
+                updated_at: env.block.time,
+                answered_in_round: env.block.height,
+            }),
+        }
+    }
+
+    // instantiate a minter which uses a price feed of v0.1 storing the given version,
+    // returns the address of the minter
+    pub fn instantiate_minter_with_legacy_price_feed(app: &mut App, version: &str) -> Addr {
+        let legacy_price_feed_code_id = app.store_code(Box::new(ContractWrapper::new(
+            PriceFeedExecute,
+            legacy_price_feed_instantiate,
+            legacy_price_feed_query,
+        )));
+        let legacy_price_feed_addr = app
+            .instantiate_contract(
+                legacy_price_feed_code_id,
+                Addr::unchecked(ADMIN),
+                &version,
+                &[],
+                "test instantiate contract",
+                None,
+            )
+            .unwrap();
+        instantiate_minter(app, legacy_price_feed_addr.as_str())
+    }
+
+    // instantiate a minter which uses the given price feed, returns the address of the minter
+    pub fn instantiate_minter(app: &mut App, price_feed: &str) -> Addr {
+        let minter_contract_code_id = app.store_code(minter_contract_template());
+        let cw20_contract_code_id = app.store_code(cw20_contract_template());
+
+        app.instantiate_contract(
+            minter_contract_code_id,
+            Addr::unchecked(ADMIN),
+            &MinterInstantiateMsg {
+                receiver_name: "aura".to_string(),
+                receiver_address: AURA.to_string(),
+                accepted_denom: NATIVE_DENOM.to_string(),
+                price_feed: price_feed.to_string(),
+                token_code_id: cw20_contract_code_id,
+                token_instantiation_msg: Cw20InstantiateMsg {
+                    name: "Stable Token".to_string(),
+                    symbol: "STV".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![],
+                    mint: None,
+                    marketing: None,
+                },
+            },
+            &[],
+            "test instantiate contract",
+            None,
+        )
+        .unwrap()
+    }
+
     // create price feed contract
     pub fn price_feed_contract_template() -> Box<dyn Contract<Empty>> {
         let contract = ContractWrapper::new(PriceFeedExecute, PriceFeedInstantiate, PriceFeedQuery);
@@ -101,6 +241,9 @@ pub mod env {
                     controller: CONTROLLER.to_string(),
                     decimals: 6,
                     description: "AURA / VND".to_string(),
+                    heartbeat: None,
+                    retention_policy: None,
+                    circuit_breaker: None,
                 },
                 &[],
                 "test instantiate contract",
@@ -110,7 +253,6 @@ pub mod env {
         // add contract info to the vector
         contract_info_vec.push(ContractInfo {
             contract_addr: price_feed_contract_addr.to_string(),
-            contract_code_id: price_feed_contract_code_id,
         });
 
         // instantiate minter contract
@@ -142,7 +284,6 @@ pub mod env {
         // add contract info to the vector
         contract_info_vec.push(ContractInfo {
             contract_addr: minter_contract_addr.to_string(),
-            contract_code_id: minter_contract_code_id,
         });
 
         (app, contract_info_vec)
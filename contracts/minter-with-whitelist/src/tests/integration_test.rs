@@ -11,23 +11,26 @@ mod tests {
     };
 
     mod price_feed_testing {
+        use price_feed::state::Role;
+
         use super::*;
 
         #[test]
-        fn only_owner_can_change_controller() {
+        fn only_owner_can_add_writer() {
             // get integration test app and contracts
             let (mut app, contracts) = instantiate_contracts();
             let price_feed_contract_addr = &contracts[0].contract_addr;
 
-            // prepare the update controller message
-            let update_controller_msg = PriceFeedExecuteMsg::UpdateController {
-                controller: CONTROLLER_FAKE.to_string(),
+            // prepare the add role message
+            let add_writer_msg = PriceFeedExecuteMsg::AddRole {
+                role: Role::Writer,
+                address: CONTROLLER_FAKE.to_string(),
             };
 
             let res = app.execute_contract(
                 Addr::unchecked(USER1),
                 Addr::unchecked(price_feed_contract_addr),
-                &update_controller_msg,
+                &add_writer_msg,
                 &[],
             );
             assert!(res.is_err());
@@ -35,10 +38,21 @@ mod tests {
             let res = app.execute_contract(
                 Addr::unchecked(ADMIN),
                 Addr::unchecked(price_feed_contract_addr),
-                &update_controller_msg,
+                &add_writer_msg,
                 &[],
             );
             assert!(res.is_ok());
+
+            // both writers can update the answer
+            for writer in [CONTROLLER, CONTROLLER_FAKE] {
+                let res = app.execute_contract(
+                    Addr::unchecked(writer),
+                    Addr::unchecked(price_feed_contract_addr),
+                    &PriceFeedExecuteMsg::UpdateRoundData { answer: 100000u64 },
+                    &[],
+                );
+                assert!(res.is_ok());
+            }
         }
 
         #[test]
@@ -86,7 +100,13 @@ mod tests {
     }
 
     mod minter_with_whitelist {
+        use crate::error::ContractError;
         use crate::msg::{ExchangingInfoResponse, QueryMsg};
+        use crate::tests::env_setup::env::{
+            instantiate_minter, instantiate_minter_with_legacy_price_feed, AURA,
+        };
+        use cosmwasm_std::{BlockInfo, Decimal};
+        use price_feed::state::CircuitBreaker;
 
         use super::*;
 
@@ -163,5 +183,464 @@ mod tests {
             // the balance should be 500000000
             assert_eq!(res.balance, Uint128::from(500000000u64));
         }
+
+        #[test]
+        fn user_cannot_exchange_with_stale_price() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let minter_contract_addr = &contracts[1].contract_addr;
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            // the price must be updated every 60 seconds
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &PriceFeedExecuteMsg::UpdateHeartbeat {
+                    heartbeat: Some(60),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let update_answer_msg = PriceFeedExecuteMsg::UpdateRoundData {
+                answer: 10000000u64,
+            };
+
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER),
+                Addr::unchecked(price_feed_contract_addr),
+                &update_answer_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // ADMIN send 100 native token to USER1
+            let res = app.send_tokens(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(USER1),
+                &coins(100, NATIVE_DENOM),
+            );
+            assert!(res.is_ok());
+
+            // the price is not updated for 2 minutes
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(120),
+                height: app.block_info().height + 24,
+                chain_id: app.block_info().chain_id,
+            });
+
+            // prepare the exchange message
+            let exchange_msg = ExecuteMsg::Exchange {
+                amount: Uint128::from(50u64),
+                expected_received: Uint128::from(500000000u64),
+            };
+
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(minter_contract_addr),
+                &exchange_msg,
+                &coins(50, NATIVE_DENOM),
+            );
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::StalePrice {}.to_string()
+            );
+
+            // the exchange works again after the price is updated
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER),
+                Addr::unchecked(price_feed_contract_addr),
+                &update_answer_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(minter_contract_addr),
+                &exchange_msg,
+                &coins(50, NATIVE_DENOM),
+            );
+            assert!(res.is_ok());
+        }
+
+        #[test]
+        fn user_cannot_exchange_when_circuit_open() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let minter_contract_addr = &contracts[1].contract_addr;
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            // the price cannot move more than 10% in a round
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &PriceFeedExecuteMsg::UpdateCircuitBreaker {
+                    circuit_breaker: CircuitBreaker {
+                        max_deviation: Some(Decimal::percent(10)),
+                        min_answer: None,
+                        max_answer: None,
+                    },
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // the second answer doubles the price, so it trips the circuit breaker
+            for answer in [10000000u64, 20000000u64] {
+                let res = app.execute_contract(
+                    Addr::unchecked(CONTROLLER),
+                    Addr::unchecked(price_feed_contract_addr),
+                    &PriceFeedExecuteMsg::UpdateRoundData { answer },
+                    &[],
+                );
+                assert!(res.is_ok());
+            }
+
+            // ADMIN send 100 native token to USER1
+            let res = app.send_tokens(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(USER1),
+                &coins(100, NATIVE_DENOM),
+            );
+            assert!(res.is_ok());
+
+            // prepare the exchange message
+            let exchange_msg = ExecuteMsg::Exchange {
+                amount: Uint128::from(50u64),
+                expected_received: Uint128::from(500000000u64),
+            };
+
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(minter_contract_addr),
+                &exchange_msg,
+                &coins(50, NATIVE_DENOM),
+            );
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::CircuitOpen {}.to_string()
+            );
+
+            // the owner rejects the quarantined answer, so the exchange works with the old price
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &PriceFeedExecuteMsg::ResetCircuitBreaker { accept: false },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(minter_contract_addr),
+                &exchange_msg,
+                &coins(50, NATIVE_DENOM),
+            );
+            assert!(res.is_ok());
+        }
+
+        #[test]
+        fn user_can_exchange_token_with_legacy_price_feed() {
+            // the price feed is not migrated yet, so it has no heartbeat and no circuit breaker
+            let (mut app, _) = instantiate_contracts();
+            let minter_contract_addr = instantiate_minter_with_legacy_price_feed(&mut app, "0.1.0");
+
+            // ADMIN send 50 native token to USER1
+            let res = app.send_tokens(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(USER1),
+                &coins(50, NATIVE_DENOM),
+            );
+            assert!(res.is_ok());
+
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                minter_contract_addr,
+                &ExecuteMsg::Exchange {
+                    amount: Uint128::from(50u64),
+                    expected_received: Uint128::from(500000000u64),
+                },
+                &coins(50, NATIVE_DENOM),
+            );
+            assert!(res.is_ok());
+        }
+
+        #[test]
+        fn user_cannot_exchange_when_price_feed_query_fails() {
+            // a price feed of v0.2 must answer the circuit breaker query, a failure halts the exchanges
+            let (mut app, _) = instantiate_contracts();
+            let minter_contract_addr = instantiate_minter_with_legacy_price_feed(&mut app, "0.2.0");
+
+            // ADMIN send 50 native token to USER1
+            let res = app.send_tokens(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(USER1),
+                &coins(50, NATIVE_DENOM),
+            );
+            assert!(res.is_ok());
+
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                minter_contract_addr,
+                &ExecuteMsg::Exchange {
+                    amount: Uint128::from(50u64),
+                    expected_received: Uint128::from(500000000u64),
+                },
+                &coins(50, NATIVE_DENOM),
+            );
+            assert!(res.is_err());
+        }
+
+        #[test]
+        fn user_cannot_exchange_without_price_feed() {
+            // the latest round data cannot be queried from an address which is not a price feed
+            let (mut app, _) = instantiate_contracts();
+            let minter_contract_addr = instantiate_minter(&mut app, AURA);
+
+            // ADMIN send 50 native token to USER1
+            let res = app.send_tokens(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(USER1),
+                &coins(50, NATIVE_DENOM),
+            );
+            assert!(res.is_ok());
+
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                minter_contract_addr,
+                &ExecuteMsg::Exchange {
+                    amount: Uint128::from(50u64),
+                    expected_received: Uint128::from(500000000u64),
+                },
+                &coins(50, NATIVE_DENOM),
+            );
+            assert!(res.is_err());
+        }
+    }
+
+    mod ownership_transfer {
+        use cosmwasm_std::StdResult;
+
+        use crate::msg::QueryMsg;
+        use crate::tests::env_setup::env::USER1;
+        use ownership::Ownership;
+
+        use super::*;
+
+        #[test]
+        fn owner_can_transfer_ownership() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let minter_contract_addr = &contracts[1].contract_addr;
+
+            // the owner proposes a new owner, who accepts the ownership
+            for (sender, msg) in [
+                (
+                    ADMIN,
+                    ExecuteMsg::ProposeNewOwner {
+                        new_owner: USER1.to_string(),
+                        expiry: None,
+                    },
+                ),
+                (USER1, ExecuteMsg::AcceptOwnership {}),
+            ] {
+                let res = app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(minter_contract_addr),
+                    &msg,
+                    &[],
+                );
+                assert!(res.is_ok());
+            }
+
+            let res: Ownership = app
+                .wrap()
+                .query_wasm_smart(minter_contract_addr, &QueryMsg::Ownership {})
+                .unwrap();
+            assert_eq!(res.owner, Some(Addr::unchecked(USER1)));
+            let res: String = app
+                .wrap()
+                .query_wasm_smart(minter_contract_addr, &QueryMsg::Owner {})
+                .unwrap();
+            assert_eq!(res, USER1);
+
+            // the owner query fails once the ownership is renounced
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(minter_contract_addr),
+                &ExecuteMsg::RenounceOwnership {},
+                &[],
+            );
+            assert!(res.is_ok());
+            let res: StdResult<String> = app
+                .wrap()
+                .query_wasm_smart(minter_contract_addr, &QueryMsg::Owner {});
+            assert!(res.is_err());
+            let res: Ownership = app
+                .wrap()
+                .query_wasm_smart(minter_contract_addr, &QueryMsg::Ownership {})
+                .unwrap();
+            assert_eq!(res.owner, None);
+        }
+    }
+
+    mod migration {
+        use crate::error::ContractError;
+        use crate::msg::{MigrateMsg, QueryMsg, ReceiverResponse};
+        use crate::tests::env_setup::env::instantiate_legacy_minter;
+
+        use super::*;
+
+        #[test]
+        fn migrate_from_v0_1() {
+            // get integration test app and a minter of v0.1
+            let (mut app, _) = instantiate_contracts();
+            let (minter_contract_addr, minter_contract_code_id) =
+                instantiate_legacy_minter(&mut app, "0.1.0");
+
+            let res = app.migrate_contract(
+                Addr::unchecked(ADMIN),
+                minter_contract_addr.clone(),
+                &MigrateMsg {},
+                minter_contract_code_id,
+            );
+            assert!(res.is_ok());
+
+            // the owner moves to the ownership and the receiver is kept
+            let res: String = app
+                .wrap()
+                .query_wasm_smart(&minter_contract_addr, &QueryMsg::Owner {})
+                .unwrap();
+            assert_eq!(res, ADMIN);
+            let res: ReceiverResponse = app
+                .wrap()
+                .query_wasm_smart(&minter_contract_addr, &QueryMsg::Receiver {})
+                .unwrap();
+            assert_eq!(res.name, "receiver");
+            assert_eq!(res.address, AURA);
+
+            // a newer version cannot be downgraded
+            let (newer_contract_addr, minter_contract_code_id) =
+                instantiate_legacy_minter(&mut app, "9.0.0");
+            let res = app.migrate_contract(
+                Addr::unchecked(ADMIN),
+                newer_contract_addr,
+                &MigrateMsg {},
+                minter_contract_code_id,
+            );
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::CannotDowngrade {
+                    version: "9.0.0".to_string()
+                }
+                .to_string()
+            );
+        }
+    }
+
+    mod pause {
+        use crate::error::ContractError;
+        use crate::msg::{ExchangingInfoResponse, QueryMsg};
+        use crate::state::Operation;
+        use crate::tests::env_setup::env::GUARDIAN;
+        use cosmwasm_std::StdResult;
+
+        use super::*;
+
+        #[test]
+        fn guardian_can_pause_exchange() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let minter_contract_addr = &contracts[1].contract_addr;
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER),
+                Addr::unchecked(price_feed_contract_addr),
+                &PriceFeedExecuteMsg::UpdateRoundData {
+                    answer: 10000000u64,
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+            let res = app.send_tokens(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(USER1),
+                &coins(50, NATIVE_DENOM),
+            );
+            assert!(res.is_ok());
+
+            // only the owner can set the guardian
+            for (sender, authorized) in [(USER1, false), (ADMIN, true)] {
+                let res = app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(minter_contract_addr),
+                    &ExecuteMsg::UpdateGuardian {
+                        guardian: Some(GUARDIAN.to_string()),
+                    },
+                    &[],
+                );
+                assert_eq!(res.is_ok(), authorized);
+            }
+
+            // only the owner or the guardian can pause the exchange
+            for (sender, authorized) in [(USER1, false), (GUARDIAN, true)] {
+                let res = app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(minter_contract_addr),
+                    &ExecuteMsg::Pause {
+                        operation: Operation::Exchange,
+                    },
+                    &[],
+                );
+                assert_eq!(res.is_ok(), authorized);
+            }
+            let res: Vec<String> = app
+                .wrap()
+                .query_wasm_smart(minter_contract_addr, &QueryMsg::PausedOperations {})
+                .unwrap();
+            assert_eq!(res, vec!["exchange".to_string()]);
+
+            // the exchange is paused, but the queries keep working
+            let exchange_msg = ExecuteMsg::Exchange {
+                amount: Uint128::from(50u64),
+                expected_received: Uint128::from(500000000u64),
+            };
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(minter_contract_addr),
+                &exchange_msg,
+                &coins(50, NATIVE_DENOM),
+            );
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::Paused {}.to_string()
+            );
+            let res: StdResult<ExchangingInfoResponse> = app
+                .wrap()
+                .query_wasm_smart(minter_contract_addr, &QueryMsg::ExchangingInfo {});
+            assert!(res.is_ok());
+
+            // only the owner can resume the exchange
+            for (sender, authorized) in [(GUARDIAN, false), (ADMIN, true)] {
+                let res = app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(minter_contract_addr),
+                    &ExecuteMsg::Unpause {
+                        operation: Operation::Exchange,
+                    },
+                    &[],
+                );
+                assert_eq!(res.is_ok(), authorized);
+            }
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(minter_contract_addr),
+                &exchange_msg,
+                &coins(50, NATIVE_DENOM),
+            );
+            assert!(res.is_ok());
+        }
     }
 }
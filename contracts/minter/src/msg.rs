@@ -1,6 +1,10 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Addr, Uint128};
 use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use cw_utils::Expiration;
+use ownership::Ownership;
+
+use crate::state::Operation;
 
 /// Message type for `instantiate` entry_point
 /// Maybe we don't need a new cw20 contract, just use the cw20-base contract
@@ -22,8 +26,32 @@ pub enum ExecuteMsg {
         expected_received: Uint128,
     },
     Withdraw {},
+    // only the owner can set the guardian, none removes the guardian
+    UpdateGuardian {
+        guardian: Option<String>,
+    },
+    // pause an operation in an emergency, only the owner or the guardian can do it
+    Pause {
+        operation: Operation,
+    },
+    // resume a paused operation, only the owner can do it
+    Unpause {
+        operation: Operation,
+    },
+    // propose a new owner, who must accept the ownership before the expiry
+    ProposeNewOwner {
+        new_owner: String,
+        expiry: Option<Expiration>,
+    },
+    AcceptOwnership {},
+    // give up the ownership permanently
+    RenounceOwnership {},
 }
 
+/// Message type for `migrate` entry_point
+#[cw_serde]
+pub struct MigrateMsg {}
+
 /// Message type for `query` entry_point
 #[cw_serde]
 #[derive(QueryResponses)]
@@ -34,6 +62,12 @@ pub enum QueryMsg {
     Receiver {},
     #[returns(ExchangingInfoResponse)]
     ExchangingInfo {},
+    #[returns(Option<Addr>)]
+    Guardian {},
+    #[returns(Vec<String>)]
+    PausedOperations {},
+    #[returns(Ownership)]
+    Ownership {},
 }
 
 #[cw_serde]
@@ -1,11 +1,26 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Empty};
+use cw_storage_plus::{Item, Map};
 
 #[cw_serde]
 pub struct Config {
-    pub owner: Addr,
     pub receiver_name: String,
+    // the guardian can pause the operations in an emergency, like the owner
+    pub guardian: Option<Addr>,
+}
+
+// the operations which can be paused independently
+#[cw_serde]
+pub enum Operation {
+    Exchange,
+}
+
+impl Operation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operation::Exchange => "exchange",
+        }
+    }
 }
 
 /// The information of exchanging
@@ -23,3 +38,5 @@ pub struct ExchangingInfo {
 
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const EXCHANGING_INFO: Item<ExchangingInfo> = Item::new("exchanging_info");
+// the paused operations, keyed by the operation name
+pub const PAUSED_OPERATIONS: Map<&str, Empty> = Map::new("paused_operations");
@@ -1,18 +1,29 @@
 #[cfg(test)]
 pub mod env {
-    use cosmwasm_std::{Addr, Coin, Empty, Uint128};
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{
+        to_binary, Addr, Binary, Coin, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+        Uint128,
+    };
 
     use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
 
     use crate::contract::{
-        execute as MinterExecute, instantiate as MinterInstantiate, query as MinterQuery,
-        reply as MinterReply,
+        execute as MinterExecute, instantiate as MinterInstantiate, migrate as MinterMigrate,
+        query as MinterQuery, reply as MinterReply,
     };
+    use crate::migrations::v0_1;
+    use crate::state::{ExchangingInfo, EXCHANGING_INFO};
 
     use price_feed::contract::{
         execute as PriceFeedExecute, instantiate as PriceFeedInstantiate, query as PriceFeedQuery,
     };
 
+    use price_feed_proxy::contract::{
+        execute as PriceFeedProxyExecute, instantiate as PriceFeedProxyInstantiate,
+        query as PriceFeedProxyQuery,
+    };
+
     use cw20_base::contract::{
         execute as Cw20Execute, instantiate as Cw20Instantiate, query as Cw20Query,
     };
@@ -20,13 +31,15 @@ pub mod env {
     use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
 
     use crate::msg::InstantiateMsg as MinterInstantiateMsg;
-    use price_feed::msg::InstantiateMsg as PriceFeedInstantiateMsg;
+    use price_feed::msg::{InstantiateMsg as PriceFeedInstantiateMsg, RoundDataResponse};
+    use price_feed_proxy::msg::InstantiateMsg as PriceFeedProxyInstantiateMsg;
 
     pub const ADMIN: &str = "aura1000000000000000000000000000000000admin";
     pub const USER1: &str = "aura1000000000000000000000000000000000user1";
     pub const AURA: &str = "aura10000000000000000000000000000000000aura";
     pub const CONTROLLER: &str = "aura10000000000000000000000000000controller";
     pub const CONTROLLER_FAKE: &str = "aura10000000000000000000000000000fake";
+    pub const GUARDIAN: &str = "aura100000000000000000000000000000guardian";
 
     pub const NATIVE_DENOM: &str = "uaura";
     pub const NATIVE_BALANCE: u128 = 1_000_000_000_000u128;
@@ -36,7 +49,6 @@ pub mod env {
 
     pub struct ContractInfo {
         pub contract_addr: String,
-        pub contract_code_id: u64,
     }
 
     // create app instance and init balance of NATIVE token for admin
@@ -65,16 +77,160 @@ pub mod env {
     // create minter contract
     pub fn minter_contract_template() -> Box<dyn Contract<Empty>> {
         let contract = ContractWrapper::new(MinterExecute, MinterInstantiate, MinterQuery)
-            .with_reply(MinterReply);
+            .with_reply(MinterReply)
+            .with_migrate(MinterMigrate);
         Box::new(contract)
     }
 
+    // the minter of v0.1 writes its config in the old layout and stores the given version
+    fn legacy_instantiate(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        version: String,
+    ) -> StdResult<Response> {
+        cw2::set_contract_version(deps.storage, "crates.io:minter", version)?;
+        v0_1::CONFIG.save(
+            deps.storage,
+            &v0_1::Config {
+                owner: info.sender,
+                receiver_name: "receiver".to_string(),
+            },
+        )?;
+        EXCHANGING_INFO.save(
+            deps.storage,
+            &ExchangingInfo {
+                accepted_denom: NATIVE_DENOM.to_string(),
+                receiver_address: Addr::unchecked(AURA),
+                token_address: Addr::unchecked(AURA),
+                price_feed: Addr::unchecked(CONTROLLER),
+            },
+        )?;
+        Ok(Response::new())
+    }
+
+    // instantiate a legacy minter which stores the given version and can be migrated
+    // by the admin, returns its address and the code id of the current minter
+    pub fn instantiate_legacy_minter(app: &mut App, version: &str) -> (Addr, u64) {
+        let legacy_contract_code_id = app.store_code(Box::new(ContractWrapper::new(
+            MinterExecute,
+            legacy_instantiate,
+            MinterQuery,
+        )));
+        let minter_contract_code_id = app.store_code(minter_contract_template());
+        let legacy_contract_addr = app
+            .instantiate_contract(
+                legacy_contract_code_id,
+                Addr::unchecked(ADMIN),
+                &version,
+                &[],
+                "test instantiate contract",
+                Some(ADMIN.to_string()),
+            )
+            .unwrap();
+        (legacy_contract_addr, minter_contract_code_id)
+    }
+
+    // the price feed of v0.1 only answers the latest round data,
+    // it does not support the heartbeat and the circuit breaker queries,
+    // its contract version is set by the test to tell it apart from a broken price feed
+    #[cw_serde]
+    pub enum LegacyPriceFeedQueryMsg {
+        LastestRoundData {},
+    }
+
+    fn legacy_price_feed_instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        version: String,
+    ) -> StdResult<Response> {
+        cw2::set_contract_version(deps.storage, "crates.io:price-feed", version)?;
+        Ok(Response::new())
+    }
+
+    fn legacy_price_feed_query(
+        _deps: Deps,
+        env: Env,
+        msg: LegacyPriceFeedQueryMsg,
+    ) -> StdResult<Binary> {
+        match msg {
+            LegacyPriceFeedQueryMsg::LastestRoundData {} => to_binary(&RoundDataResponse {
+                round_id: env.block.height,
+                answer: Uint128::from(10000000u64),
+                updated_at: env.block.time,
+                answered_in_round: env.block.height,
+            }),
+        }
+    }
+
+    // instantiate a minter which uses a price feed of v0.1 storing the given version,
+    // returns the address of the minter
+    pub fn instantiate_minter_with_legacy_price_feed(app: &mut App, version: &str) -> Addr {
+        let legacy_price_feed_code_id = app.store_code(Box::new(ContractWrapper::new(
+            PriceFeedExecute,
+            legacy_price_feed_instantiate,
+            legacy_price_feed_query,
+        )));
+        let legacy_price_feed_addr = app
+            .instantiate_contract(
+                legacy_price_feed_code_id,
+                Addr::unchecked(ADMIN),
+                &version,
+                &[],
+                "test instantiate contract",
+                None,
+            )
+            .unwrap();
+        instantiate_minter(app, legacy_price_feed_addr.as_str())
+    }
+
+    // instantiate a minter which uses the given price feed, returns the address of the minter
+    pub fn instantiate_minter(app: &mut App, price_feed: &str) -> Addr {
+        let minter_contract_code_id = app.store_code(minter_contract_template());
+        let cw20_contract_code_id = app.store_code(cw20_contract_template());
+
+        app.instantiate_contract(
+            minter_contract_code_id,
+            Addr::unchecked(ADMIN),
+            &MinterInstantiateMsg {
+                receiver_name: "aura".to_string(),
+                receiver_address: AURA.to_string(),
+                accepted_denom: NATIVE_DENOM.to_string(),
+                price_feed: price_feed.to_string(),
+                token_code_id: cw20_contract_code_id,
+                token_instantiation_msg: Cw20InstantiateMsg {
+                    name: "Stable Token".to_string(),
+                    symbol: "STV".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![],
+                    mint: None,
+                    marketing: None,
+                },
+            },
+            &[],
+            "test instantiate contract",
+            None,
+        )
+        .unwrap()
+    }
+
     // create price feed contract
     pub fn price_feed_contract_template() -> Box<dyn Contract<Empty>> {
         let contract = ContractWrapper::new(PriceFeedExecute, PriceFeedInstantiate, PriceFeedQuery);
         Box::new(contract)
     }
 
+    // create price feed proxy contract
+    pub fn price_feed_proxy_contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            PriceFeedProxyExecute,
+            PriceFeedProxyInstantiate,
+            PriceFeedProxyQuery,
+        );
+        Box::new(contract)
+    }
+
     // create cw20 contract
     pub fn cw20_contract_template() -> Box<dyn Contract<Empty>> {
         let contract = ContractWrapper::new(Cw20Execute, Cw20Instantiate, Cw20Query);
@@ -91,6 +247,8 @@ pub mod env {
         let minter_contract_code_id = app.store_code(minter_contract_template());
         let price_feed_contract_code_id = app.store_code(price_feed_contract_template());
         let cw20_contract_code_id = app.store_code(cw20_contract_template());
+        let price_feed_proxy_contract_code_id =
+            app.store_code(price_feed_proxy_contract_template());
 
         // instantiate price feed contract
         let price_feed_contract_addr = app
@@ -101,6 +259,9 @@ pub mod env {
                     controller: CONTROLLER.to_string(),
                     decimals: 6,
                     description: "AURA / VND".to_string(),
+                    heartbeat: None,
+                    retention_policy: None,
+                    circuit_breaker: None,
                 },
                 &[],
                 "test instantiate contract",
@@ -110,7 +271,6 @@ pub mod env {
         // add contract info to the vector
         contract_info_vec.push(ContractInfo {
             contract_addr: price_feed_contract_addr.to_string(),
-            contract_code_id: price_feed_contract_code_id,
         });
 
         // instantiate minter contract
@@ -142,7 +302,57 @@ pub mod env {
         // add contract info to the vector
         contract_info_vec.push(ContractInfo {
             contract_addr: minter_contract_addr.to_string(),
-            contract_code_id: minter_contract_code_id,
+        });
+
+        // instantiate price feed proxy contract pointing to the price feed
+        let price_feed_proxy_contract_addr = app
+            .instantiate_contract(
+                price_feed_proxy_contract_code_id,
+                Addr::unchecked(ADMIN),
+                &PriceFeedProxyInstantiateMsg {
+                    aggregator: price_feed_contract_addr.to_string(),
+                    delay: None,
+                },
+                &[],
+                "test instantiate contract",
+                None,
+            )
+            .unwrap();
+
+        // add contract info to the vector
+        contract_info_vec.push(ContractInfo {
+            contract_addr: price_feed_proxy_contract_addr.to_string(),
+        });
+
+        // instantiate minter contract using the price feed proxy
+        let proxied_minter_contract_addr = app
+            .instantiate_contract(
+                minter_contract_code_id,
+                Addr::unchecked(ADMIN),
+                &MinterInstantiateMsg {
+                    receiver_name: "aura".to_string(),
+                    receiver_address: AURA.to_string(),
+                    accepted_denom: NATIVE_DENOM.to_string(),
+                    price_feed: price_feed_proxy_contract_addr.to_string(),
+                    token_code_id: cw20_contract_code_id,
+                    token_instantiation_msg: Cw20InstantiateMsg {
+                        name: "Stable Token".to_string(),
+                        symbol: "STV".to_string(),
+                        decimals: 6,
+                        initial_balances: vec![],
+                        mint: None,
+                        marketing: None,
+                    },
+                },
+                &[],
+                "test instantiate contract",
+                None,
+            )
+            .unwrap();
+
+        // add contract info to the vector
+        contract_info_vec.push(ContractInfo {
+            contract_addr: proxied_minter_contract_addr.to_string(),
         });
 
         (app, contract_info_vec)
@@ -153,6 +363,6 @@ pub mod env {
         let (_app, contract_info_vec) = instantiate_contracts();
 
         // check if all contracts are instantiated
-        assert_eq!(contract_info_vec.len(), 2);
+        assert_eq!(contract_info_vec.len(), 4);
     }
 }
@@ -0,0 +1,306 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Uint128,
+};
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Bound;
+use ownership::{
+    accept_ownership, get_ownership, initialize_owner, is_owner, propose_new_owner,
+    renounce_ownership,
+};
+use semver::Version;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, PairId, PairResponse, QueryMsg, RoundDataResponse,
+};
+use crate::state::{PairInfo, RoundData, PAIRS, ROUND_DATA};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:oracle-registry";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// the default and max number of pairs returned by the Pairs query
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+/// Handling contract instantiation
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // the sender is the first owner
+    initialize_owner(deps.storage, deps.api, info.sender.as_str())?;
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("owner", info.sender))
+}
+
+/// Handling contract execution
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::AddPair {
+            pair,
+            controller,
+            decimals,
+            description,
+        } => add_pair(deps, env, info, pair, controller, decimals, description),
+        ExecuteMsg::UpdateController { pair, controller } => {
+            update_controller(deps, env, info, pair, controller)
+        }
+        ExecuteMsg::UpdateRoundData { pair, answer } => {
+            update_round_data(deps, env, info, pair, answer)
+        }
+        ExecuteMsg::ProposeNewOwner { new_owner, expiry } => {
+            Ok(propose_new_owner(deps, &env, &info, new_owner, expiry)?)
+        }
+        ExecuteMsg::AcceptOwnership {} => Ok(accept_ownership(deps, &env, &info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(renounce_ownership(deps, &env, &info)?),
+    }
+}
+
+/// Handling contract migration
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // only an older or the same version of the oracle registry can be migrated
+    let previous = get_contract_version(deps.storage)?;
+    if previous.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigration {
+            contract: previous.contract,
+            version: previous.version,
+        });
+    }
+    let previous_version: Version = previous.version.parse()?;
+    if previous_version > CONTRACT_VERSION.parse::<Version>()? {
+        return Err(ContractError::CannotDowngrade {
+            version: previous.version,
+        });
+    }
+
+    // the state layout has not changed since the first version
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "migrate"),
+        ("previous_version", previous.version.as_str()),
+        ("version", CONTRACT_VERSION),
+    ]))
+}
+
+/// Handling contract query
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Ownership {} => to_binary(&get_ownership(deps.storage)?),
+        QueryMsg::LastestRoundData { pair } => {
+            to_binary(&query_lastest_round_data(deps, env, pair)?)
+        }
+        QueryMsg::RoundData { pair, round_id } => {
+            to_binary(&query_round_data(deps, pair, round_id)?)
+        }
+        QueryMsg::Decimals { pair } => to_binary(&load_pair(deps, &pair)?.decimals),
+        QueryMsg::Description { pair } => to_binary(&load_pair(deps, &pair)?.description),
+        QueryMsg::Controller { pair } => to_binary(&load_pair(deps, &pair)?.controller),
+        QueryMsg::Pairs { start_after, limit } => {
+            to_binary(&query_pairs(deps, start_after, limit)?)
+        }
+    }
+}
+
+pub fn add_pair(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    pair: PairId,
+    controller: String,
+    decimals: u8,
+    description: String,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // the base and the quote must not be empty
+    validate_pair(&pair)?;
+
+    // each pair can be added only once
+    if PAIRS.has(deps.storage, (&pair.base, &pair.quote)) {
+        return Err(ContractError::PairAlreadyExists {});
+    }
+
+    // init pair info, the pair has no round yet
+    let pair_info = PairInfo {
+        controller: deps.api.addr_validate(&controller)?,
+        latest_round: 0,
+        decimals,
+        description,
+    };
+    PAIRS.save(deps.storage, (&pair.base, &pair.quote), &pair_info)?;
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "add_pair"),
+        ("base", &pair.base),
+        ("quote", &pair.quote),
+        ("controller", &controller),
+    ]))
+}
+
+pub fn update_controller(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    pair: PairId,
+    controller: String,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // update the controller of the pair
+    let mut pair_info = PAIRS
+        .may_load(deps.storage, (&pair.base, &pair.quote))?
+        .ok_or(ContractError::PairNotFound {})?;
+    pair_info.controller = deps.api.addr_validate(&controller)?;
+    PAIRS.save(deps.storage, (&pair.base, &pair.quote), &pair_info)?;
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "update_controller"),
+        ("base", &pair.base),
+        ("quote", &pair.quote),
+        ("controller", &controller),
+    ]))
+}
+
+pub fn update_round_data(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: PairId,
+    answer: u64,
+) -> Result<Response, ContractError> {
+    // check if the sender is the controller of the pair
+    let mut pair_info = PAIRS
+        .may_load(deps.storage, (&pair.base, &pair.quote))?
+        .ok_or(ContractError::PairNotFound {})?;
+    if info.sender != pair_info.controller {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // the round id follows the latest round id of the pair
+    let latest_round_id = pair_info.latest_round + 1;
+
+    // lastest round data
+    let round_data = RoundData {
+        answer: Uint128::from(answer),
+        updated_at: env.block.time,
+    };
+
+    // save the round data
+    ROUND_DATA.save(
+        deps.storage,
+        (&pair.base, &pair.quote, latest_round_id),
+        &round_data,
+    )?;
+
+    // update the latest round id in the pair info
+    pair_info.latest_round = latest_round_id;
+    PAIRS.save(deps.storage, (&pair.base, &pair.quote), &pair_info)?;
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "update_round_data"),
+        ("base", &pair.base),
+        ("quote", &pair.quote),
+        ("round_id", &latest_round_id.to_string()),
+        ("answer", &answer.to_string()),
+    ]))
+}
+
+fn validate_pair(pair: &PairId) -> Result<(), ContractError> {
+    if pair.base.trim().is_empty() || pair.quote.trim().is_empty() {
+        return Err(ContractError::InvalidPair {});
+    }
+    Ok(())
+}
+
+fn load_pair(deps: Deps, pair: &PairId) -> StdResult<PairInfo> {
+    PAIRS.load(deps.storage, (&pair.base, &pair.quote))
+}
+
+pub fn query_lastest_round_data(
+    deps: Deps,
+    _env: Env,
+    pair: PairId,
+) -> StdResult<RoundDataResponse> {
+    // load the latest round id from the pair info
+    let latest_round_id = load_pair(deps, &pair)?.latest_round;
+    // load the round data from the round data map
+    let lastest_round_data =
+        ROUND_DATA.load(deps.storage, (&pair.base, &pair.quote, latest_round_id))?;
+
+    let res = RoundDataResponse {
+        round_id: latest_round_id,
+        answer: lastest_round_data.answer,
+        updated_at: lastest_round_data.updated_at,
+        answered_in_round: latest_round_id,
+    };
+    // return the round data
+    Ok(res)
+}
+
+pub fn query_round_data(deps: Deps, pair: PairId, round_id: u64) -> StdResult<RoundDataResponse> {
+    // load the round data from the round data map
+    let round_data = ROUND_DATA.load(deps.storage, (&pair.base, &pair.quote, round_id))?;
+
+    let res = RoundDataResponse {
+        round_id,
+        answer: round_data.answer,
+        updated_at: round_data.updated_at,
+        answered_in_round: round_id,
+    };
+    // return the round data
+    Ok(res)
+}
+
+pub fn query_pairs(
+    deps: Deps,
+    start_after: Option<PairId>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PairResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .as_ref()
+        .map(|pair| Bound::exclusive((pair.base.as_str(), pair.quote.as_str())));
+
+    PAIRS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let ((base, quote), pair_info) = item?;
+            Ok(PairResponse {
+                pair: PairId { base, quote },
+                controller: pair_info.controller.to_string(),
+                latest_round: pair_info.latest_round,
+                decimals: pair_info.decimals,
+                description: pair_info.description,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,79 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw_utils::Expiration;
+use ownership::Ownership;
+
+pub use price_feed::msg::RoundDataResponse;
+
+/// Message type for `instantiate` entry_point
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+// the identifier of a pair, e.g. AURA / USD
+#[cw_serde]
+pub struct PairId {
+    pub base: String,
+    pub quote: String,
+}
+
+/// Message type for `execute` entry_point
+#[cw_serde]
+pub enum ExecuteMsg {
+    AddPair {
+        pair: PairId,
+        controller: String,
+        decimals: u8,
+        description: String,
+    },
+    UpdateController {
+        pair: PairId,
+        controller: String,
+    },
+    UpdateRoundData {
+        pair: PairId,
+        answer: u64,
+    },
+    // propose a new owner, who must accept the ownership before the expiry
+    ProposeNewOwner {
+        new_owner: String,
+        expiry: Option<Expiration>,
+    },
+    AcceptOwnership {},
+    // give up the ownership permanently
+    RenounceOwnership {},
+}
+
+/// Message type for `migrate` entry_point
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// Message type for `query` entry_point
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(RoundDataResponse)]
+    LastestRoundData { pair: PairId },
+    #[returns(RoundDataResponse)]
+    RoundData { pair: PairId, round_id: u64 },
+    #[returns(u8)]
+    Decimals { pair: PairId },
+    #[returns(String)]
+    Description { pair: PairId },
+    #[returns(String)]
+    Controller { pair: PairId },
+    #[returns(Vec<PairResponse>)]
+    Pairs {
+        start_after: Option<PairId>,
+        limit: Option<u32>,
+    },
+    #[returns(Ownership)]
+    Ownership {},
+}
+
+#[cw_serde]
+pub struct PairResponse {
+    pub pair: PairId,
+    pub controller: String,
+    pub latest_round: u64,
+    pub decimals: u8,
+    pub description: String,
+}
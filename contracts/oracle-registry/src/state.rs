@@ -0,0 +1,25 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cw_storage_plus::Map;
+
+// information of each pair in the registry
+#[cw_serde]
+pub struct PairInfo {
+    pub controller: Addr,
+    pub latest_round: u64,
+    pub decimals: u8,
+    pub description: String,
+}
+
+// the data struct of each round
+#[cw_serde]
+pub struct RoundData {
+    pub answer: Uint128,
+    pub updated_at: Timestamp,
+}
+
+// the pairs are stored in the map with the (base, quote) as the key
+pub const PAIRS: Map<(&str, &str), PairInfo> = Map::new("pairs");
+// the round data is stored in the map with the (base, quote, round id) as the key,
+// the round ids of each pair start from 1
+pub const ROUND_DATA: Map<(&str, &str, u64), RoundData> = Map::new("round_data");
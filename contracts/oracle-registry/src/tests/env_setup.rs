@@ -0,0 +1,102 @@
+#[cfg(test)]
+pub mod env {
+    use cosmwasm_std::{Addr, Empty};
+
+    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+
+    use crate::contract::{execute, instantiate, migrate, query};
+    use crate::msg::{ExecuteMsg, InstantiateMsg, PairId};
+
+    pub const ADMIN: &str = "aura1000000000000000000000000000000000admin";
+    pub const USER1: &str = "aura1000000000000000000000000000000000user1";
+    pub const CONTROLLER: &str = "aura10000000000000000000000000000controller";
+    pub const CONTROLLER_2: &str = "aura1000000000000000000000000000controller2";
+
+    pub struct ContractInfo {
+        pub contract_addr: String,
+        pub contract_code_id: u64,
+    }
+
+    pub fn aura_usd() -> PairId {
+        PairId {
+            base: "AURA".to_string(),
+            quote: "USD".to_string(),
+        }
+    }
+
+    pub fn aura_vnd() -> PairId {
+        PairId {
+            base: "AURA".to_string(),
+            quote: "VND".to_string(),
+        }
+    }
+
+    // create app instance
+    fn mock_app() -> App {
+        AppBuilder::new().build(|_, _, _| {})
+    }
+
+    // create oracle registry contract
+    pub fn oracle_registry_contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(execute, instantiate, query).with_migrate(migrate);
+        Box::new(contract)
+    }
+
+    pub fn instantiate_contracts() -> (App, Vec<ContractInfo>) {
+        // Create a new app instance
+        let mut app = mock_app();
+        // Create a vector to store all contract info ([oracle registry] - [0])
+        let mut contract_info_vec: Vec<ContractInfo> = Vec::new();
+
+        // store code of all contracts to the app and get the code ids
+        let oracle_registry_contract_code_id = app.store_code(oracle_registry_contract_template());
+
+        // instantiate oracle registry contract
+        let oracle_registry_contract_addr = app
+            .instantiate_contract(
+                oracle_registry_contract_code_id,
+                Addr::unchecked(ADMIN),
+                &InstantiateMsg {},
+                &[],
+                "test instantiate contract",
+                Some(ADMIN.to_string()),
+            )
+            .unwrap();
+
+        // add contract info to the vector
+        contract_info_vec.push(ContractInfo {
+            contract_addr: oracle_registry_contract_addr.to_string(),
+            contract_code_id: oracle_registry_contract_code_id,
+        });
+
+        // add AURA / USD and AURA / VND pairs with their own controllers
+        for (pair, controller, decimals) in
+            [(aura_usd(), CONTROLLER, 6), (aura_vnd(), CONTROLLER_2, 0)]
+        {
+            let exec_msg = ExecuteMsg::AddPair {
+                description: format!("{} / {}", pair.base, pair.quote),
+                pair,
+                controller: controller.to_string(),
+                decimals,
+            };
+            app.execute_contract(
+                Addr::unchecked(ADMIN),
+                oracle_registry_contract_addr.clone(),
+                &exec_msg,
+                &[],
+            )
+            .unwrap();
+        }
+
+        // return the app instance and contract info vector
+        (app, contract_info_vec)
+    }
+
+    #[test]
+    fn test_instantiate_contracts() {
+        let (_app, contract_info_vec) = instantiate_contracts();
+
+        // check if all contracts are instantiated
+        assert_eq!(contract_info_vec.len(), 1);
+    }
+}
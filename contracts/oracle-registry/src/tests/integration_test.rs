@@ -0,0 +1,359 @@
+#![cfg(test)]
+mod tests {
+    use crate::msg::{ExecuteMsg, PairId, PairResponse, QueryMsg, RoundDataResponse};
+    use crate::tests::env_setup::env::{
+        aura_usd, aura_vnd, instantiate_contracts, ADMIN, CONTROLLER, CONTROLLER_2, USER1,
+    };
+    use cosmwasm_std::{Addr, BlockInfo, StdResult, Uint128};
+    use cw_multi_test::Executor;
+
+    mod oracle_registry_testing {
+        use crate::ContractError;
+
+        use super::*;
+
+        #[test]
+        fn only_owner_can_add_pair() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let oracle_registry_contract_addr = &contracts[0].contract_addr;
+
+            // prepare the add pair message
+            let add_pair_msg = ExecuteMsg::AddPair {
+                pair: PairId {
+                    base: "ATOM".to_string(),
+                    quote: "USD".to_string(),
+                },
+                controller: CONTROLLER.to_string(),
+                decimals: 6,
+                description: "ATOM / USD".to_string(),
+            };
+
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(oracle_registry_contract_addr),
+                &add_pair_msg,
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(oracle_registry_contract_addr),
+                &add_pair_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // the pair cannot be added twice
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(oracle_registry_contract_addr),
+                &add_pair_msg,
+                &[],
+            );
+            assert!(res.is_err());
+
+            // the base and the quote of the pair must not be empty
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(oracle_registry_contract_addr),
+                &ExecuteMsg::AddPair {
+                    pair: PairId {
+                        base: "ATOM".to_string(),
+                        quote: " ".to_string(),
+                    },
+                    controller: CONTROLLER.to_string(),
+                    decimals: 6,
+                    description: "ATOM / ?".to_string(),
+                },
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::InvalidPair {}.to_string()
+            );
+
+            // the pairs are listed by (base, quote)
+            let res: Vec<PairResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    oracle_registry_contract_addr,
+                    &QueryMsg::Pairs {
+                        start_after: None,
+                        limit: Some(2),
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                res.iter().map(|p| p.pair.clone()).collect::<Vec<_>>(),
+                vec![
+                    PairId {
+                        base: "ATOM".to_string(),
+                        quote: "USD".to_string(),
+                    },
+                    aura_usd(),
+                ]
+            );
+
+            let res: Vec<PairResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    oracle_registry_contract_addr,
+                    &QueryMsg::Pairs {
+                        start_after: Some(aura_usd()),
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.len(), 1);
+            assert_eq!(res[0].pair, aura_vnd());
+            assert_eq!(res[0].controller, CONTROLLER_2);
+        }
+
+        #[test]
+        fn only_controller_of_pair_can_update_answer() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let oracle_registry_contract_addr = &contracts[0].contract_addr;
+
+            // prepare the update round data message
+            let update_answer_msg = ExecuteMsg::UpdateRoundData {
+                pair: aura_usd(),
+                answer: 100000u64,
+            };
+
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER_2),
+                Addr::unchecked(oracle_registry_contract_addr),
+                &update_answer_msg,
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER),
+                Addr::unchecked(oracle_registry_contract_addr),
+                &update_answer_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // the pair which is not registered cannot be updated
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER),
+                Addr::unchecked(oracle_registry_contract_addr),
+                &ExecuteMsg::UpdateRoundData {
+                    pair: PairId {
+                        base: "ATOM".to_string(),
+                        quote: "USD".to_string(),
+                    },
+                    answer: 100000u64,
+                },
+                &[],
+            );
+            assert!(res.is_err());
+
+            // the owner can hand the pair over to another controller
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(oracle_registry_contract_addr),
+                &ExecuteMsg::UpdateController {
+                    pair: aura_usd(),
+                    controller: CONTROLLER_2.to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER_2),
+                Addr::unchecked(oracle_registry_contract_addr),
+                &update_answer_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+        }
+
+        #[test]
+        fn each_pair_has_its_own_round_history() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let oracle_registry_contract_addr = &contracts[0].contract_addr;
+
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER),
+                Addr::unchecked(oracle_registry_contract_addr),
+                &ExecuteMsg::UpdateRoundData {
+                    pair: aura_usd(),
+                    answer: 100u64,
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // increase the block height to simulate the time passed
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(60),
+                height: app.block_info().height + 12,
+                chain_id: app.block_info().chain_id,
+            });
+
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER),
+                Addr::unchecked(oracle_registry_contract_addr),
+                &ExecuteMsg::UpdateRoundData {
+                    pair: aura_usd(),
+                    answer: 200u64,
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER_2),
+                Addr::unchecked(oracle_registry_contract_addr),
+                &ExecuteMsg::UpdateRoundData {
+                    pair: aura_vnd(),
+                    answer: 2500u64,
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // the round ids of each pair start from 1
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    oracle_registry_contract_addr,
+                    &QueryMsg::LastestRoundData { pair: aura_usd() },
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(200u64));
+            assert_eq!(res.round_id, 2);
+
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    oracle_registry_contract_addr,
+                    &QueryMsg::LastestRoundData { pair: aura_vnd() },
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(2500u64));
+            assert_eq!(res.round_id, 1);
+
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    oracle_registry_contract_addr,
+                    &QueryMsg::RoundData {
+                        pair: aura_usd(),
+                        round_id: 1,
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(100u64));
+            assert_eq!(res.answered_in_round, 1);
+
+            // AURA / VND has no second round
+            let res: StdResult<RoundDataResponse> = app.wrap().query_wasm_smart(
+                oracle_registry_contract_addr,
+                &QueryMsg::RoundData {
+                    pair: aura_vnd(),
+                    round_id: 2,
+                },
+            );
+            assert!(res.is_err());
+
+            let res: u8 = app
+                .wrap()
+                .query_wasm_smart(
+                    oracle_registry_contract_addr,
+                    &QueryMsg::Decimals { pair: aura_vnd() },
+                )
+                .unwrap();
+            assert_eq!(res, 0);
+        }
+    }
+
+    mod ownership_transfer {
+        use crate::tests::env_setup::env::USER1;
+        use ownership::Ownership;
+
+        use super::*;
+
+        #[test]
+        fn owner_can_transfer_ownership() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let oracle_registry_contract_addr = &contracts[0].contract_addr;
+
+            // the owner proposes a new owner, who accepts the ownership
+            for (sender, msg) in [
+                (
+                    ADMIN,
+                    ExecuteMsg::ProposeNewOwner {
+                        new_owner: USER1.to_string(),
+                        expiry: None,
+                    },
+                ),
+                (USER1, ExecuteMsg::AcceptOwnership {}),
+            ] {
+                let res = app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(oracle_registry_contract_addr),
+                    &msg,
+                    &[],
+                );
+                assert!(res.is_ok());
+            }
+
+            let res: Ownership = app
+                .wrap()
+                .query_wasm_smart(oracle_registry_contract_addr, &QueryMsg::Ownership {})
+                .unwrap();
+            assert_eq!(res.owner, Some(Addr::unchecked(USER1)));
+        }
+    }
+
+    mod migration {
+        use crate::msg::MigrateMsg;
+
+        use super::*;
+
+        #[test]
+        fn admin_can_migrate_and_keep_pairs() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let oracle_registry_contract_addr = &contracts[0].contract_addr;
+            let oracle_registry_contract_code_id = contracts[0].contract_code_id;
+
+            // only the admin of the contract can migrate it
+            for (sender, authorized) in [(USER1, false), (ADMIN, true)] {
+                let res = app.migrate_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(oracle_registry_contract_addr),
+                    &MigrateMsg {},
+                    oracle_registry_contract_code_id,
+                );
+                assert_eq!(res.is_ok(), authorized);
+            }
+
+            // the pairs are kept
+            let res: Vec<PairResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    oracle_registry_contract_addr,
+                    &QueryMsg::Pairs {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                res.iter().map(|p| p.pair.clone()).collect::<Vec<_>>(),
+                vec![aura_usd(), aura_vnd()]
+            );
+        }
+    }
+}
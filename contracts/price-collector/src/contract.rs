@@ -1,14 +1,31 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, WasmMsg,
+    to_binary, Addr, BankMsg, Binary, Coin, Decimal, Deps, DepsMut, Empty, Env, Event, MessageInfo,
+    Response, StdResult, Storage, Uint128, WasmMsg,
 };
-use cw2::set_contract_version;
-use cw_storage_plus::Bound;
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::{Bound, Map};
+use cw_utils::must_pay;
+use ownership::{
+    accept_ownership, get_ownership, initialize_owner, is_owner, propose_new_owner,
+    renounce_ownership,
+};
+use semver::Version;
+use sha2::{Digest, Sha256};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{Answer, RoundData, CONFIG, FEEDERS, PRICE_FEED_INFO, ROUND_DATA};
+use crate::migrations::{backfill_round_indexes, migrate_from_v0_1};
+use crate::msg::{
+    ExecuteMsg, FeederResponse, FeederStatsResponse, InstantiateMsg, MigrateMsg, OrderBy, QueryMsg,
+    Report, ReportSignature, RoundResponse,
+};
+use crate::state::{
+    AggregationStrategy, Answer, Config, Feeder, FeederStats, ManualRound, Operation, RoundData,
+    RoundDataStatus, StakingConfig, Unbonding, COMMITS, CONFIG, FEEDERS, FEEDER_STATS,
+    MANUAL_ROUNDS, PAUSED_OPERATIONS, PRICE_FEED_INFO, REWARDS, REWARD_POOL, ROUND_DATA,
+    ROUND_IDS_BY_HEIGHT, ROUND_IDS_BY_TIME, UNBONDINGS,
+};
 
 use price_feed::msg::ExecuteMsg as PriceFeedExecuteMsg;
 
@@ -16,30 +33,51 @@ use price_feed::msg::ExecuteMsg as PriceFeedExecuteMsg;
 const CONTRACT_NAME: &str = "crates.io:price-collector";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-pub const MAX_DIFF_DURRATION: u64 = 300; // the max height diff between 2 round ids is 5 minutes, 300 = (12 * 5) * 5
+// the default and max number of feeders or rounds returned by the ListFeeders and Rounds queries
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+pub const DEFAULT_ROUND_DURATION: u64 = 300; // the max height diff between 2 round ids is 5 minutes, 300 = (12 * 5) * 5
+const DEFAULT_MIGRATION_LIMIT: u32 = 1000; // the default number of old rounds indexed by each migration
 
 /// Handling contract instantiation
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    env: Env,
+    _env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    // the sender is the first owner
+    initialize_owner(deps.storage, deps.api, info.sender.as_str())?;
+
     // init config
-    let config = crate::state::Config {
-        owner: info.sender.clone(),
+    let config = Config {
+        aggregation: msg.aggregation.unwrap_or(AggregationStrategy::Median {}),
+        deviation_band: msg.deviation_band.unwrap_or(Decimal::percent(10)),
+        quorum: msg.quorum.unwrap_or(Decimal::from_ratio(2u128, 3u128)),
+        round_duration: msg.round_duration.unwrap_or(DEFAULT_ROUND_DURATION),
+        reward: msg.reward,
+        staking: msg.staking,
+        commit_duration: msg.commit_duration,
+        keeper_tip: msg.keeper_tip,
+        guardian: None,
+        force_disabled: false,
     };
+    if !config.is_valid() {
+        return Err(ContractError::InvalidConfig {});
+    }
     CONFIG.save(deps.storage, &config)?;
 
-    // init price feed info
+    // init price feed info, the first round id is 1
     let price_feed_info = crate::state::PriceFeedInfo {
-        price_feed: deps.api.addr_validate(&msg.price_feed)?,
-        latest_round: env.block.height,
+        price_feeds: vec![deps.api.addr_validate(&msg.price_feed)?],
+        latest_round: 0,
     };
     PRICE_FEED_INFO.save(deps.storage, &price_feed_info)?;
+    REWARD_POOL.save(deps.storage, &Uint128::zero())?;
 
     Ok(Response::new()
         .add_attribute("method", "instantiate")
@@ -59,39 +97,187 @@ pub fn execute(
         ExecuteMsg::UpdatePriceFeeder {
             price_feeder,
             status,
-        } => update_price_feed(deps, env, info, price_feeder, status),
+            moniker,
+        } => update_price_feed(deps, env, info, price_feeder, status, moniker),
         ExecuteMsg::ProvideRoundData { answer } => update_round_data(deps, env, info, answer),
+        ExecuteMsg::CommitRoundData { commitment } => {
+            commit_round_data(deps, env, info, commitment)
+        }
+        ExecuteMsg::RevealRoundData { answer, salt } => {
+            reveal_round_data(deps, env, info, answer, salt)
+        }
+        ExecuteMsg::UpdateConfig {
+            aggregation,
+            deviation_band,
+            quorum,
+            round_duration,
+            reward,
+            staking,
+            commit_duration,
+            keeper_tip,
+        } => update_config(
+            deps,
+            env,
+            info,
+            aggregation,
+            deviation_band,
+            quorum,
+            round_duration,
+            reward,
+            staking,
+            commit_duration,
+            keeper_tip,
+        ),
+        ExecuteMsg::RegisterPubkey { pubkey } => register_pubkey(deps, env, info, pubkey),
+        ExecuteMsg::SubmitReport { report, signatures } => {
+            submit_report(deps, env, info, report, signatures)
+        }
+        ExecuteMsg::FundRewards {} => fund_rewards(deps, env, info),
+        ExecuteMsg::ClaimRewards {} => claim_rewards(deps, env, info),
+        ExecuteMsg::Bond { moniker } => bond(deps, env, info, moniker),
+        ExecuteMsg::Unbond {} => unbond(deps, env, info),
+        ExecuteMsg::WithdrawUnbonded {} => withdraw_unbonded(deps, env, info),
+        ExecuteMsg::FinalizeRound {} => finalize_round(deps, env, info),
+        ExecuteMsg::UpdateGuardian { guardian } => update_guardian(deps, env, info, guardian),
+        ExecuteMsg::AddPriceFeed { price_feed } => add_price_feed(deps, env, info, price_feed),
+        ExecuteMsg::RemovePriceFeed { price_feed } => {
+            remove_price_feed(deps, env, info, price_feed)
+        }
+        ExecuteMsg::ForceRoundData { answer, reason } => {
+            force_round_data(deps, env, info, answer, reason)
+        }
+        ExecuteMsg::DisableForceRoundData {} => disable_force_round_data(deps, env, info),
+        ExecuteMsg::Pause { operation } => pause(deps, env, info, operation),
+        ExecuteMsg::Unpause { operation } => unpause(deps, env, info, operation),
+        ExecuteMsg::ProposeNewOwner { new_owner, expiry } => {
+            Ok(propose_new_owner(deps, &env, &info, new_owner, expiry)?)
+        }
+        ExecuteMsg::AcceptOwnership {} => Ok(accept_ownership(deps, &env, &info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(renounce_ownership(deps, &env, &info)?),
+    }
+}
+
+/// Handling contract migration
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    // only an older or the same version of the price collector can be migrated
+    let previous = get_contract_version(deps.storage)?;
+    if previous.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigration {
+            contract: previous.contract,
+            version: previous.version,
+        });
+    }
+    let previous_version: Version = previous.version.parse()?;
+    if previous_version > CONTRACT_VERSION.parse::<Version>()? {
+        return Err(ContractError::CannotDowngrade {
+            version: previous.version,
+        });
     }
+
+    // transform the state of each previous version in order
+    if previous_version < Version::new(0, 2, 0) {
+        migrate_from_v0_1(deps.branch(), &env)?;
+    }
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // the rounds of v0.1 are indexed in batches, the migration is repeated until it is finished
+    let (indexed_rounds, backfill_finished) =
+        backfill_round_indexes(deps.storage, msg.limit.unwrap_or(DEFAULT_MIGRATION_LIMIT))?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "migrate"),
+        ("previous_version", previous.version.as_str()),
+        ("version", CONTRACT_VERSION),
+        ("indexed_rounds", &indexed_rounds.to_string()),
+        ("backfill_finished", &backfill_finished.to_string()),
+    ]))
 }
 
 /// Handling contract query
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
+        QueryMsg::Ownership {} => to_binary(&get_ownership(deps.storage)?),
         QueryMsg::LastestRoundData {} => to_binary(&query_lastest_round_data(deps, env)?),
         QueryMsg::RoundData { round_id } => to_binary(&query_round_data(deps, round_id)?),
+        QueryMsg::Rounds {
+            start_after,
+            limit,
+            order,
+        } => to_binary(&query_rounds(deps, start_after, limit, order)?),
+        QueryMsg::RoundAtHeight { height } => {
+            to_binary(&query_round_at(deps, &ROUND_IDS_BY_HEIGHT, height)?)
+        }
+        QueryMsg::RoundAtTime { time } => {
+            to_binary(&query_round_at(deps, &ROUND_IDS_BY_TIME, time.seconds())?)
+        }
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::PausedOperations {} => to_binary(&query_paused_operations(deps)?),
+        QueryMsg::PriceFeeds {} => to_binary(&PRICE_FEED_INFO.load(deps.storage)?.price_feeds),
+        QueryMsg::ManualRound { round_id } => {
+            to_binary(&MANUAL_ROUNDS.may_load(deps.storage, round_id)?)
+        }
+        QueryMsg::ReportHash { report } => {
+            to_binary(&Binary::from(report_hash(&env.contract.address, &report)?))
+        }
+        QueryMsg::CommitmentHash {
+            feeder,
+            answer,
+            salt,
+        } => to_binary(&Binary::from(commitment_hash(
+            &deps.api.addr_validate(&feeder)?,
+            answer,
+            &salt,
+        ))),
+        QueryMsg::Feeder { address } => to_binary(&query_feeder(deps, address)?),
+        QueryMsg::ListFeeders { start_after, limit } => {
+            to_binary(&query_list_feeders(deps, start_after, limit)?)
+        }
+        QueryMsg::FeederStats { address } => to_binary(&query_feeder_stats(deps, address)?),
+        QueryMsg::RewardPool {} => {
+            to_binary(&REWARD_POOL.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::PendingRewards { address } => to_binary(&query_pending_rewards(deps, address)?),
+        QueryMsg::Unbonding { address } => {
+            to_binary(&UNBONDINGS.may_load(deps.storage, deps.api.addr_validate(&address)?)?)
+        }
     }
 }
 
 pub fn update_price_feed(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     price_feeder: String,
     status: bool,
+    moniker: Option<String>,
 ) -> Result<Response, ContractError> {
     // check if the sender is the owner
-    let config = crate::state::CONFIG.load(deps.storage)?;
-    if info.sender != config.owner {
+    if !is_owner(deps.storage, &info.sender)? {
         return Err(ContractError::Unauthorized {});
     }
 
-    // update the status of the price feed in the list
-    if status {
-        FEEDERS.save(deps.storage, deps.api.addr_validate(&price_feeder)?, &true)?;
-    } else {
-        FEEDERS.remove(deps.storage, deps.api.addr_validate(&price_feeder)?);
+    // update the status of the price feed in the list, a removed feeder is kept as inactive
+    let feeder_addr = deps.api.addr_validate(&price_feeder)?;
+    let mut feeder = FEEDERS
+        .may_load(deps.storage, feeder_addr.clone())?
+        .unwrap_or(Feeder {
+            moniker: String::new(),
+            pubkey: None,
+            joined_height: env.block.height,
+            active: false,
+            last_submission_height: None,
+            bond: Uint128::zero(),
+        });
+    if status && !feeder.active {
+        feeder.joined_height = env.block.height;
     }
+    feeder.active = status;
+    if let Some(moniker) = moniker {
+        feeder.moniker = moniker;
+    }
+    FEEDERS.save(deps.storage, feeder_addr, &feeder)?;
 
     // return the response
     Ok(Response::new().add_attributes([
@@ -101,151 +287,1170 @@ pub fn update_price_feed(
     ]))
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn update_config(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    aggregation: Option<AggregationStrategy>,
+    deviation_band: Option<Decimal>,
+    quorum: Option<Decimal>,
+    round_duration: Option<u64>,
+    reward: Option<Coin>,
+    staking: Option<StakingConfig>,
+    commit_duration: Option<u64>,
+    keeper_tip: Option<Coin>,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // only update the fields which are set
+    if let Some(aggregation) = aggregation {
+        config.aggregation = aggregation;
+    }
+    if let Some(deviation_band) = deviation_band {
+        config.deviation_band = deviation_band;
+    }
+    if let Some(quorum) = quorum {
+        config.quorum = quorum;
+    }
+    if let Some(round_duration) = round_duration {
+        config.round_duration = round_duration;
+    }
+    if let Some(reward) = reward {
+        // the reward denom cannot be changed, because the pool and the allocated rewards are in that denom
+        if matches!(&config.reward, Some(current) if current.denom != reward.denom) {
+            return Err(ContractError::InvalidConfig {});
+        }
+        config.reward = Some(reward);
+    }
+    if let Some(staking) = staking {
+        // the bond denom cannot be changed, because the bonds are in that denom
+        if matches!(&config.staking, Some(current) if current.min_bond.denom != staking.min_bond.denom)
+        {
+            return Err(ContractError::InvalidConfig {});
+        }
+        config.staking = Some(staking);
+    }
+    if let Some(commit_duration) = commit_duration {
+        config.commit_duration = (commit_duration > 0).then_some(commit_duration);
+    }
+    if let Some(keeper_tip) = keeper_tip {
+        config.keeper_tip = Some(keeper_tip);
+    }
+    if !config.is_valid() {
+        return Err(ContractError::InvalidConfig {});
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "update_config"),
+        ("deviation_band", &config.deviation_band.to_string()),
+        ("quorum", &config.quorum.to_string()),
+        ("round_duration", &config.round_duration.to_string()),
+    ]))
+}
+
 pub fn update_round_data(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     answer: u64,
 ) -> Result<Response, ContractError> {
-    // only a feeder can call this method
-    if !FEEDERS.has(deps.storage, info.sender.clone()) {
-        return Err(ContractError::Unauthorized {});
+    assert_not_paused(deps.storage, Operation::ProvideRoundData)?;
+
+    // the answers must be committed first in the commit-reveal mode
+    let config = CONFIG.load(deps.storage)?;
+    if config.commit_duration.is_some() {
+        return Err(ContractError::CommitRevealRequired {});
     }
 
+    // only a feeder can call this method
+    record_submission(deps.storage, &info.sender, env.block.height)?;
+
     // load latest round id
     let latest_round_id = PRICE_FEED_INFO.load(deps.storage)?.latest_round;
 
-    if (latest_round_id > env.block.height - MAX_DIFF_DURRATION)
-        && ROUND_DATA.has(deps.storage, latest_round_id)
-    {
+    if is_live_round(deps.storage, &config, latest_round_id, env.block.height)? {
         // The lastest round is not expired yet, so just add new answer to the round data
-        // load the RoundData
-        let mut round_data = ROUND_DATA.load(deps.storage, latest_round_id)?;
+        let msgs = add_answer(
+            deps.storage,
+            &env,
+            &config,
+            latest_round_id,
+            info.sender,
+            answer,
+        )?;
+
+        Ok(Response::new()
+            .add_messages(msgs)
+            .add_attribute("method", "update_round_data")
+            .add_attribute("round_id", latest_round_id.to_string())
+            .add_attribute("answer", answer.to_string()))
+    } else {
+        // if previous round data is pending, just process it first
+        let msgs = close_expired_round(deps.storage, &env, &config, latest_round_id)?;
+
+        // The lastest round is expired, so create new round data
+        let round_id = start_round(
+            deps.storage,
+            &env,
+            vec![Answer {
+                provider: info.sender,
+                value: answer,
+                updated_at_height: env.block.height,
+            }],
+        )?;
+
+        Ok(Response::new().add_messages(msgs).add_attributes([
+            ("method", "update_round_data"),
+            ("round_id", round_id.to_string().as_str()),
+            ("answer", answer.to_string().as_str()),
+        ]))
+    }
+}
 
-        // if the round data is ended
-        if round_data.status != crate::state::RoundDataStatus::Pending {
+pub fn commit_round_data(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    commitment: Binary,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, Operation::ProvideRoundData)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let commit_duration = config
+        .commit_duration
+        .ok_or(ContractError::CommitRevealDisabled {})?;
+
+    // only a feeder can call this method
+    record_submission(deps.storage, &info.sender, env.block.height)?;
+
+    let latest_round_id = PRICE_FEED_INFO.load(deps.storage)?.latest_round;
+    let mut res = Response::new();
+    let round_id = if is_live_round(deps.storage, &config, latest_round_id, env.block.height)? {
+        // the commits are accepted only in the commit phase of a pending round
+        let round_data = ROUND_DATA.load(deps.storage, latest_round_id)?;
+        if round_data.status != RoundDataStatus::Pending {
             return Err(ContractError::RoundEnded {});
         }
+        if env.block.height >= round_data.started_at_height + commit_duration {
+            return Err(ContractError::InvalidPhase {});
+        }
+        latest_round_id
+    } else {
+        // the first commit after the latest round expires starts a new round
+        res = res.add_messages(close_expired_round(
+            deps.storage,
+            &env,
+            &config,
+            latest_round_id,
+        )?);
+        start_round(deps.storage, &env, vec![])?
+    };
+
+    // a feeder can only commit once in a round
+    if COMMITS.has(deps.storage, (round_id, &info.sender)) {
+        return Err(ContractError::InvalidUpdate {});
+    }
+    COMMITS.save(deps.storage, (round_id, &info.sender), &commitment)?;
+
+    Ok(res.add_attributes([
+        ("method", "commit_round_data"),
+        ("round_id", round_id.to_string().as_str()),
+        ("feeder", info.sender.as_str()),
+    ]))
+}
+
+pub fn reveal_round_data(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    answer: u64,
+    salt: String,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, Operation::ProvideRoundData)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let commit_duration = config
+        .commit_duration
+        .ok_or(ContractError::CommitRevealDisabled {})?;
+
+    // only a feeder can call this method
+    record_submission(deps.storage, &info.sender, env.block.height)?;
+
+    // the answers are revealed after the commit phase of the live round
+    let latest_round_id = PRICE_FEED_INFO.load(deps.storage)?.latest_round;
+    if !is_live_round(deps.storage, &config, latest_round_id, env.block.height)?
+        || env.block.height
+            < ROUND_DATA
+                .load(deps.storage, latest_round_id)?
+                .started_at_height
+                + commit_duration
+    {
+        return Err(ContractError::InvalidPhase {});
+    }
+
+    // the answer must match the commitment, otherwise the feeder misses the round
+    let commitment = COMMITS
+        .may_load(deps.storage, (latest_round_id, &info.sender))?
+        .ok_or(ContractError::InvalidReveal {})?;
+    if commitment.as_slice() != commitment_hash(&info.sender, answer, &salt).as_slice() {
+        return Err(ContractError::InvalidReveal {});
+    }
+    COMMITS.remove(deps.storage, (latest_round_id, &info.sender));
+
+    let msgs = add_answer(
+        deps.storage,
+        &env,
+        &config,
+        latest_round_id,
+        info.sender,
+        answer,
+    )?;
+
+    Ok(Response::new().add_messages(msgs).add_attributes([
+        ("method", "reveal_round_data"),
+        ("round_id", latest_round_id.to_string().as_str()),
+        ("answer", answer.to_string().as_str()),
+    ]))
+}
+
+pub fn register_pubkey(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    pubkey: Binary,
+) -> Result<Response, ContractError> {
+    // only a feeder can register its public key
+    let mut feeder = load_active_feeder(deps.storage, &info.sender)?;
+
+    // the public key must be a compressed (33 bytes) or uncompressed (65 bytes) secp256k1 key
+    if pubkey.len() != 33 && pubkey.len() != 65 {
+        return Err(ContractError::InvalidPubkey {});
+    }
+    feeder.pubkey = Some(pubkey);
+    FEEDERS.save(deps.storage, info.sender.clone(), &feeder)?;
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "register_pubkey"),
+        ("feeder", info.sender.as_str()),
+    ]))
+}
+
+pub fn submit_report(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    report: Report,
+    signatures: Vec<ReportSignature>,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage, Operation::ProvideRoundData)?;
+
+    let config = CONFIG.load(deps.storage)?;
+
+    // the report must be for the next round, so it cannot be replayed
+    let latest_round_id = PRICE_FEED_INFO.load(deps.storage)?.latest_round;
+    if report.round_id != latest_round_id + 1 {
+        return Err(ContractError::InvalidReport {});
+    }
+
+    // the feeders are still answering the live round
+    if is_live_round(deps.storage, &config, latest_round_id, env.block.height)?
+        && ROUND_DATA.load(deps.storage, latest_round_id)?.status == RoundDataStatus::Pending
+    {
+        return Err(ContractError::RoundInProgress {});
+    }
+
+    // each observation must be signed by its feeder with the registered public key
+    let hash = report_hash(&env.contract.address, &report)?;
+    let mut answers: Vec<Answer> = vec![];
+    for observation in report.observations {
+        let feeder = deps.api.addr_validate(&observation.feeder)?;
+        if answers.iter().any(|a| a.provider == feeder) {
+            return Err(ContractError::InvalidReport {});
+        }
 
-        // if the round data is already answered by the sender
-        if round_data.is_answered(info.sender.clone()) {
-            return Err(ContractError::InvalidUpdate {});
+        let mut feeder_info = load_active_feeder(deps.storage, &feeder)?;
+        let pubkey = feeder_info
+            .pubkey
+            .clone()
+            .ok_or(ContractError::InvalidSignature {})?;
+        let signature = signatures
+            .iter()
+            .find(|s| s.feeder == observation.feeder)
+            .ok_or(ContractError::InvalidSignature {})?;
+        if !deps
+            .api
+            .secp256k1_verify(&hash, &signature.signature, &pubkey)
+            .unwrap_or(false)
+        {
+            return Err(ContractError::InvalidSignature {});
         }
+        feeder_info.last_submission_height = Some(env.block.height);
+        FEEDERS.save(deps.storage, feeder.clone(), &feeder_info)?;
 
-        // create new answer
-        let new_answer = Answer {
-            provider: info.sender,
-            value: answer,
+        answers.push(Answer {
+            provider: feeder,
+            value: observation.value,
             updated_at_height: env.block.height,
-        };
-
-        round_data.answers.push(new_answer.clone());
-
-        let mut res = Response::new();
-
-        // if the number of answers is greater than 2/3 of the feeders,
-        // then push the answer for price feed contract and the status of round is answered
-        let feeders = FEEDERS
-            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-            .collect::<StdResult<Vec<_>>>()?;
-        if round_data.current_number_answeres() * 3 > feeders.len() * 2 {
-            let mut sorted_answers = vec![];
-            // sort the answers by the answer value using insertion sort
-            for answer in round_data.answers.iter() {
-                let mut i = sorted_answers.len();
-                while i > 0 && answer.value < sorted_answers[i - 1] {
-                    i -= 1;
-                }
-                sorted_answers.insert(i, answer.value);
-            }
+        });
+    }
 
-            // the new answer must be not greater than 10% of the last answer and not less than 10% of the first answer
-            if new_answer.value > sorted_answers[sorted_answers.len() - 1] * 11 / 10
-                || new_answer.value < sorted_answers[0] * 9 / 10
-            {
-                // if the answer is not valid, then the round is rejected
-                round_data.status = crate::state::RoundDataStatus::Rejected;
-                round_data.answered_at_height = env.block.height;
-            } else {
-                // if the answer is valid, then the round is answered
-                round_data.status = crate::state::RoundDataStatus::Answered;
-                round_data.answered_at_height = env.block.height;
-
-                // update the latest round id in the price feed info
-                let price_feed_info = PRICE_FEED_INFO.load(deps.storage)?;
-                // return the response
-                res = res.add_message(WasmMsg::Execute {
-                    contract_addr: price_feed_info.price_feed.to_string(),
-                    msg: to_binary(&PriceFeedExecuteMsg::UpdateRoundData {
-                        answer: round_data.current_answer(),
-                    })?,
-                    funds: vec![],
-                })
-            }
-        }
-        // just save the round data
+    let round_data = RoundData {
+        started_at_height: env.block.height,
+        status: RoundDataStatus::Answered,
+        answers,
+        answered_at_height: env.block.height,
+    };
+
+    // the signed answers must reach the quorum, even after the outliers are discarded
+    let number_feeders = number_feeders(deps.storage)?;
+    if !config.reaches_quorum(round_data.current_number_answeres(), number_feeders)
+        || !config.reaches_quorum(
+            round_data.accepted_answers(config.deviation_band).len(),
+            number_feeders,
+        )
+    {
+        return Err(ContractError::InvalidReport {});
+    }
+
+    // if previous round data is pending, process it first,
+    // then save the round data as a finalized round
+    let msgs = close_expired_round(deps.storage, &env, &config, latest_round_id)?;
+    save_new_round(deps.storage, &env, &round_data)?;
+
+    // push the answer to the price feed contract
+    let answer = round_data.current_answer(&config);
+    settle_round(deps.storage, &round_data, &config, Some(answer))?;
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_messages(update_price_feed_msgs(deps.storage, answer)?)
+        .add_attributes([
+            ("method", "submit_report"),
+            ("round_id", report.round_id.to_string().as_str()),
+            ("answer", answer.to_string().as_str()),
+        ]))
+}
+
+pub fn fund_rewards(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+
+    // the funds must be the reward coins
+    let reward = config.reward.ok_or(ContractError::InvalidConfig {})?;
+    let amount = must_pay(&info, &reward.denom)?;
+
+    let pool = REWARD_POOL.may_load(deps.storage)?.unwrap_or_default() + amount;
+    REWARD_POOL.save(deps.storage, &pool)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "fund_rewards"),
+        ("amount", amount.to_string().as_str()),
+        ("reward_pool", pool.to_string().as_str()),
+    ]))
+}
+
+pub fn claim_rewards(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // the feeder may claim its rewards even after it is removed
+    let amount = REWARDS
+        .may_load(deps.storage, info.sender.clone())?
+        .unwrap_or_default();
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    REWARDS.remove(deps.storage, info.sender.clone());
+
+    // the rewards are allocated only when the reward is configured
+    let config = CONFIG.load(deps.storage)?;
+    let reward = config.reward.ok_or(ContractError::InvalidConfig {})?;
+    let transfer_msg = BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount: vec![Coin {
+            denom: reward.denom,
+            amount,
+        }],
+    };
+
+    Ok(Response::new().add_message(transfer_msg).add_attributes([
+        ("method", "claim_rewards"),
+        ("feeder", info.sender.as_str()),
+        ("amount", amount.to_string().as_str()),
+    ]))
+}
+
+pub fn finalize_round(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let latest_round_id = PRICE_FEED_INFO.load(deps.storage)?.latest_round;
+    let mut round_data = ROUND_DATA
+        .may_load(deps.storage, latest_round_id)?
+        .ok_or(ContractError::RoundNotFinalizable {})?;
+    if round_data.status != RoundDataStatus::Pending {
+        return Err(ContractError::RoundEnded {});
+    }
+
+    // the live round is answered if its answers reach the quorum,
+    // the expired round is answered or rejected
+    let msgs = if is_live_round(deps.storage, &config, latest_round_id, env.block.height)? {
+        let msgs = answer_round(deps.storage, &env, &config, &mut round_data)?;
         ROUND_DATA.save(deps.storage, latest_round_id, &round_data)?;
+        if round_data.status != RoundDataStatus::Pending {
+            remove_commits(deps.storage, latest_round_id)?;
+        }
+        msgs
+    } else {
+        let msgs = close_expired_round(deps.storage, &env, &config, latest_round_id)?;
+        round_data = ROUND_DATA.load(deps.storage, latest_round_id)?;
+        msgs
+    };
+    if round_data.status == RoundDataStatus::Pending {
+        return Err(ContractError::RoundNotFinalizable {});
+    }
 
-        Ok(res
-            .add_attribute("method", "update_round_data")
-            .add_attribute("round_id", latest_round_id.to_string())
-            .add_attribute("answer", answer.to_string()))
+    let mut res = Response::new().add_messages(msgs);
+
+    // the keeper is tipped from the reward pool if the pool has enough coins
+    let mut tip = Uint128::zero();
+    if let Some(keeper_tip) = config.keeper_tip {
+        let pool = REWARD_POOL.may_load(deps.storage)?.unwrap_or_default();
+        if !keeper_tip.amount.is_zero() && pool >= keeper_tip.amount {
+            tip = keeper_tip.amount;
+            REWARD_POOL.save(deps.storage, &(pool - tip))?;
+            res = res.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![keeper_tip],
+            });
+        }
+    }
+
+    Ok(res.add_attributes([
+        ("method", "finalize_round"),
+        ("round_id", latest_round_id.to_string().as_str()),
+        ("keeper", info.sender.as_str()),
+        ("keeper_tip", tip.to_string().as_str()),
+    ]))
+}
+
+pub fn add_price_feed(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    price_feed: String,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // the price feed receives the answers of the next rounds
+    let price_feed_addr = deps.api.addr_validate(&price_feed)?;
+    let mut price_feed_info = PRICE_FEED_INFO.load(deps.storage)?;
+    if price_feed_info.price_feeds.contains(&price_feed_addr) {
+        return Err(ContractError::PriceFeedAlreadyExists {});
+    }
+    price_feed_info.price_feeds.push(price_feed_addr);
+    PRICE_FEED_INFO.save(deps.storage, &price_feed_info)?;
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "add_price_feed"),
+        ("price_feed", price_feed.as_str()),
+    ]))
+}
+
+pub fn remove_price_feed(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    price_feed: String,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let price_feed_addr = deps.api.addr_validate(&price_feed)?;
+    let mut price_feed_info = PRICE_FEED_INFO.load(deps.storage)?;
+    if !price_feed_info.price_feeds.contains(&price_feed_addr) {
+        return Err(ContractError::PriceFeedNotFound {});
+    }
+    price_feed_info
+        .price_feeds
+        .retain(|price_feed| price_feed != price_feed_addr);
+    PRICE_FEED_INFO.save(deps.storage, &price_feed_info)?;
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "remove_price_feed"),
+        ("price_feed", price_feed.as_str()),
+    ]))
+}
+
+pub fn update_guardian(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    guardian: Option<String>,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // update the guardian, none removes the guardian
+    config.guardian = guardian
+        .map(|guardian| deps.api.addr_validate(&guardian))
+        .transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "update_guardian"),
+        (
+            "guardian",
+            &config
+                .guardian
+                .map_or("none".to_string(), |guardian| guardian.to_string()),
+        ),
+    ]))
+}
+
+pub fn force_round_data(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    answer: u64,
+    reason: String,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner or the guardian
+    let config = CONFIG.load(deps.storage)?;
+    if !is_owner(deps.storage, &info.sender)? && Some(&info.sender) != config.guardian.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+    if config.force_disabled {
+        return Err(ContractError::ForceRoundDataDisabled {});
+    }
+    if reason.trim().is_empty() {
+        return Err(ContractError::InvalidReason {});
+    }
+
+    // the manual round is answered by the sender, and is not counted in the feeder stats
+    let round_data = RoundData {
+        started_at_height: env.block.height,
+        status: RoundDataStatus::Answered,
+        answers: vec![Answer {
+            provider: info.sender.clone(),
+            value: answer,
+            updated_at_height: env.block.height,
+        }],
+        answered_at_height: env.block.height,
+    };
+
+    // a finalized round of the current block cannot be replaced,
+    // the pending round which is still live is rejected and superseded by the manual round,
+    // the pending round which is expired is processed first
+    let latest_round_id = PRICE_FEED_INFO.load(deps.storage)?.latest_round;
+    let mut msgs = vec![];
+    if is_live_round(deps.storage, &config, latest_round_id, env.block.height)? {
+        let latest_round_data = ROUND_DATA.load(deps.storage, latest_round_id)?;
+        if latest_round_data.status == RoundDataStatus::Pending {
+            reject_round(
+                deps.storage,
+                &env,
+                &config,
+                latest_round_id,
+                latest_round_data,
+            )?;
+        } else if latest_round_data.started_at_height == env.block.height {
+            return Err(ContractError::RoundEnded {});
+        }
     } else {
-        // if previous round data is pending, just process it first
-        let mut res = Response::new();
-        if ROUND_DATA.has(deps.storage, latest_round_id) {
-            let mut round_data = ROUND_DATA.load(deps.storage, latest_round_id)?;
-
-            if round_data.status == crate::state::RoundDataStatus::Pending {
-                let feeders = FEEDERS
-                    .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-                    .collect::<StdResult<Vec<_>>>()?;
-                if round_data.current_number_answeres() * 3 > feeders.len() * 2 {
-                    // if the answer is valid, then the round is answered
-                    round_data.status = crate::state::RoundDataStatus::Answered;
-                    round_data.answered_at_height = env.block.height;
-
-                    // update the latest round id in the price feed info
-                    let price_feed_info = PRICE_FEED_INFO.load(deps.storage)?;
-                    // return the response
-                    res = res.add_message(WasmMsg::Execute {
-                        contract_addr: price_feed_info.price_feed.to_string(),
-                        msg: to_binary(&PriceFeedExecuteMsg::UpdateRoundData {
-                            answer: round_data.current_answer(),
-                        })?,
-                        funds: vec![],
-                    })
+        msgs = close_expired_round(deps.storage, &env, &config, latest_round_id)?;
+    }
+    let round_id = save_new_round(deps.storage, &env, &round_data)?;
+
+    // keep the audit trail of the manual round
+    MANUAL_ROUNDS.save(
+        deps.storage,
+        round_id,
+        &ManualRound {
+            forced_by: info.sender.clone(),
+            reason: reason.clone(),
+        },
+    )?;
+
+    // push the answer to the price feed contract
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_messages(update_price_feed_msgs(deps.storage, answer)?)
+        .add_event(Event::new("force_round_data").add_attributes([
+            ("round_id", round_id.to_string()),
+            ("answer", answer.to_string()),
+            ("forced_by", info.sender.to_string()),
+            ("reason", reason),
+        ]))
+        .add_attribute("method", "force_round_data"))
+}
+
+pub fn disable_force_round_data(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // there is no way to enable it again
+    config.force_disabled = true;
+    CONFIG.save(deps.storage, &config)?;
+
+    // return the response
+    Ok(Response::new().add_attribute("method", "disable_force_round_data"))
+}
+
+pub fn pause(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    operation: Operation,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner or the guardian
+    let config = CONFIG.load(deps.storage)?;
+    if !is_owner(deps.storage, &info.sender)? && Some(&info.sender) != config.guardian.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if PAUSED_OPERATIONS.has(deps.storage, operation.as_str()) {
+        return Err(ContractError::AlreadyPaused {});
+    }
+    PAUSED_OPERATIONS.save(deps.storage, operation.as_str(), &Empty {})?;
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "pause"),
+        ("operation", operation.as_str()),
+        ("sender", info.sender.as_str()),
+    ]))
+}
+
+pub fn unpause(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    operation: Operation,
+) -> Result<Response, ContractError> {
+    // only the owner can resume a paused operation, the guardian cannot
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !PAUSED_OPERATIONS.has(deps.storage, operation.as_str()) {
+        return Err(ContractError::NotPaused {});
+    }
+    PAUSED_OPERATIONS.remove(deps.storage, operation.as_str());
+
+    // return the response
+    Ok(Response::new().add_attributes([("method", "unpause"), ("operation", operation.as_str())]))
+}
+
+fn assert_not_paused(storage: &dyn Storage, operation: Operation) -> Result<(), ContractError> {
+    if PAUSED_OPERATIONS.has(storage, operation.as_str()) {
+        return Err(ContractError::Paused {});
+    }
+    Ok(())
+}
+
+pub fn query_paused_operations(deps: Deps) -> StdResult<Vec<String>> {
+    PAUSED_OPERATIONS
+        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect()
+}
+
+pub fn bond(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    moniker: Option<String>,
+) -> Result<Response, ContractError> {
+    // the funds must be the bond coins
+    let config = CONFIG.load(deps.storage)?;
+    let staking = config.staking.ok_or(ContractError::InvalidConfig {})?;
+    let amount = must_pay(&info, &staking.min_bond.denom)?;
+
+    let mut feeder = FEEDERS
+        .may_load(deps.storage, info.sender.clone())?
+        .unwrap_or(Feeder {
+            moniker: String::new(),
+            pubkey: None,
+            joined_height: env.block.height,
+            active: false,
+            last_submission_height: None,
+            bond: Uint128::zero(),
+        });
+    // only the owner enables the feeder, the bond never changes its status
+    feeder.bond += amount;
+    if let Some(moniker) = moniker {
+        feeder.moniker = moniker;
+    }
+    FEEDERS.save(deps.storage, info.sender.clone(), &feeder)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "bond"),
+        ("feeder", info.sender.as_str()),
+        ("amount", amount.to_string().as_str()),
+        ("bond", feeder.bond.to_string().as_str()),
+    ]))
+}
+
+pub fn unbond(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let staking = config.staking.ok_or(ContractError::InvalidConfig {})?;
+
+    let mut feeder = FEEDERS
+        .may_load(deps.storage, info.sender.clone())?
+        .ok_or(ContractError::NothingToUnbond {})?;
+    if feeder.bond.is_zero() {
+        return Err(ContractError::NothingToUnbond {});
+    }
+
+    // the feeder cannot provide answers without its bond, which is locked for the unbonding period
+    let amount = feeder.bond;
+    feeder.bond = Uint128::zero();
+    FEEDERS.save(deps.storage, info.sender.clone(), &feeder)?;
+
+    let release_at = env.block.time.plus_seconds(staking.unbonding_period);
+    let unbonding = match UNBONDINGS.may_load(deps.storage, info.sender.clone())? {
+        Some(unbonding) => Unbonding {
+            amount: unbonding.amount + amount,
+            release_at,
+        },
+        None => Unbonding { amount, release_at },
+    };
+    UNBONDINGS.save(deps.storage, info.sender.clone(), &unbonding)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "unbond"),
+        ("feeder", info.sender.as_str()),
+        ("amount", amount.to_string().as_str()),
+        ("release_at", release_at.seconds().to_string().as_str()),
+    ]))
+}
+
+pub fn withdraw_unbonded(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let unbonding = UNBONDINGS
+        .may_load(deps.storage, info.sender.clone())?
+        .ok_or(ContractError::NothingToClaim {})?;
+    if env.block.time < unbonding.release_at {
+        return Err(ContractError::UnbondingNotFinished {});
+    }
+    UNBONDINGS.remove(deps.storage, info.sender.clone());
+
+    // the unbondings exist only when the staking is set
+    let config = CONFIG.load(deps.storage)?;
+    let staking = config.staking.ok_or(ContractError::InvalidConfig {})?;
+    let transfer_msg = BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount: vec![Coin {
+            denom: staking.min_bond.denom,
+            amount: unbonding.amount,
+        }],
+    };
+
+    Ok(Response::new().add_message(transfer_msg).add_attributes([
+        ("method", "withdraw_unbonded"),
+        ("feeder", info.sender.as_str()),
+        ("amount", unbonding.amount.to_string().as_str()),
+    ]))
+}
+
+// the sha256 hash of the contract address and the json of the report
+pub fn report_hash(contract_addr: &Addr, report: &Report) -> StdResult<Vec<u8>> {
+    let mut hasher = Sha256::new();
+    hasher.update(contract_addr.as_bytes());
+    hasher.update(to_binary(report)?.as_slice());
+    Ok(hasher.finalize().to_vec())
+}
+
+// the sha256 hash of the feeder address, the answer and the salt,
+// the address is included so other feeders cannot copy the commitment
+pub fn commitment_hash(feeder: &Addr, answer: u64, salt: &str) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(feeder.as_bytes());
+    hasher.update(answer.to_be_bytes());
+    hasher.update(salt.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+// load the feeder, fails if it is not an active feeder
+fn load_active_feeder(storage: &dyn Storage, address: &Addr) -> Result<Feeder, ContractError> {
+    let config = CONFIG.load(storage)?;
+    match FEEDERS.may_load(storage, address.clone())? {
+        Some(feeder) if config.is_active_feeder(&feeder) => Ok(feeder),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+// record the submission of the feeder, fails if it is not an active feeder
+fn record_submission(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    height: u64,
+) -> Result<(), ContractError> {
+    let mut feeder = load_active_feeder(storage, address)?;
+    feeder.last_submission_height = Some(height);
+    FEEDERS.save(storage, address.clone(), &feeder)?;
+    Ok(())
+}
+
+// the round accepts new answers until it expires
+fn is_live_round(
+    storage: &dyn Storage,
+    config: &Config,
+    round_id: u64,
+    height: u64,
+) -> StdResult<bool> {
+    Ok(ROUND_DATA
+        .may_load(storage, round_id)?
+        .map_or(false, |round_data| {
+            round_data.started_at_height + config.round_duration > height
+        }))
+}
+
+// add the answer of the feeder to the live round
+fn add_answer(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    round_id: u64,
+    provider: Addr,
+    value: u64,
+) -> Result<Vec<WasmMsg>, ContractError> {
+    let mut round_data = ROUND_DATA.load(storage, round_id)?;
+
+    // if the round data is ended
+    if round_data.status != RoundDataStatus::Pending {
+        return Err(ContractError::RoundEnded {});
+    }
+
+    // if the round data is already answered by the sender
+    if round_data.is_answered(provider.clone()) {
+        return Err(ContractError::InvalidUpdate {});
+    }
+
+    round_data.answers.push(Answer {
+        provider,
+        value,
+        updated_at_height: env.block.height,
+    });
+
+    let msgs = answer_round(storage, env, config, &mut round_data)?;
+    ROUND_DATA.save(storage, round_id, &round_data)?;
+    if round_data.status != RoundDataStatus::Pending {
+        remove_commits(storage, round_id)?;
+    }
+    Ok(msgs)
+}
+
+// if the number of answers reaches the quorum of the feeders,
+// the answers which are too far from the median are discarded,
+// and the round is answered if the remaining answers still reach the quorum
+fn answer_round(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    round_data: &mut RoundData,
+) -> StdResult<Vec<WasmMsg>> {
+    let mut msgs = vec![];
+    let number_feeders = number_feeders(storage)?;
+    if config.reaches_quorum(round_data.current_number_answeres(), number_feeders)
+        && config.reaches_quorum(
+            round_data.accepted_answers(config.deviation_band).len(),
+            number_feeders,
+        )
+    {
+        round_data.status = RoundDataStatus::Answered;
+        round_data.answered_at_height = env.block.height;
+
+        // push the answer to the price feed contract
+        let final_answer = round_data.current_answer(config);
+        settle_round(storage, round_data, config, Some(final_answer))?;
+        msgs = update_price_feed_msgs(storage, final_answer)?;
+    }
+    Ok(msgs)
+}
+
+// finalize the expired round if it is still pending and its answers reach the quorum
+fn close_expired_round(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    round_id: u64,
+) -> StdResult<Vec<WasmMsg>> {
+    let mut round_data = match ROUND_DATA.may_load(storage, round_id)? {
+        Some(round_data) if round_data.status == RoundDataStatus::Pending => round_data,
+        _ => return Ok(vec![]),
+    };
+
+    // the commitments which are not revealed cannot be revealed anymore
+    remove_commits(storage, round_id)?;
+
+    let mut msgs = vec![];
+    let number_feeders = number_feeders(storage)?;
+    if config.reaches_quorum(round_data.current_number_answeres(), number_feeders) {
+        if config.reaches_quorum(
+            round_data.accepted_answers(config.deviation_band).len(),
+            number_feeders,
+        ) {
+            // if enough answers are accepted, then the round is answered
+            round_data.status = RoundDataStatus::Answered;
+
+            // push the answer to the price feed contract
+            let final_answer = round_data.current_answer(config);
+            settle_round(storage, &round_data, config, Some(final_answer))?;
+            msgs = update_price_feed_msgs(storage, final_answer)?;
+        } else {
+            // the feeders did not agree on the answer, so the round is rejected
+            round_data.status = RoundDataStatus::Rejected;
+            settle_round(storage, &round_data, config, None)?;
+        }
+        round_data.answered_at_height = env.block.height;
+        ROUND_DATA.save(storage, round_id, &round_data)?;
+    }
+    Ok(msgs)
+}
+
+// remove the remaining commitments of the round
+// reject the pending round which is superseded by a manual round,
+// the feeders are counted in the stats like in any rejected round
+fn reject_round(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    round_id: u64,
+    mut round_data: RoundData,
+) -> StdResult<()> {
+    remove_commits(storage, round_id)?;
+    round_data.status = RoundDataStatus::Rejected;
+    round_data.answered_at_height = env.block.height;
+    settle_round(storage, &round_data, config, None)?;
+    ROUND_DATA.save(storage, round_id, &round_data)
+}
+
+fn remove_commits(storage: &mut dyn Storage, round_id: u64) -> StdResult<()> {
+    let feeders = COMMITS
+        .prefix(round_id)
+        .keys(storage, None, None, cosmwasm_std::Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for feeder in feeders {
+        COMMITS.remove(storage, (round_id, &feeder));
+    }
+    Ok(())
+}
+
+// start a new round at the current block height
+fn start_round(storage: &mut dyn Storage, env: &Env, answers: Vec<Answer>) -> StdResult<u64> {
+    let round_data = RoundData {
+        started_at_height: env.block.height,
+        status: RoundDataStatus::Pending,
+        answers,
+        answered_at_height: 0,
+    };
+    save_new_round(storage, env, &round_data)
+}
+
+// save the round data with the next round id, indexed by the current block height and time
+fn save_new_round(storage: &mut dyn Storage, env: &Env, round_data: &RoundData) -> StdResult<u64> {
+    let mut price_feed_info = PRICE_FEED_INFO.load(storage)?;
+    let round_id = price_feed_info.latest_round + 1;
+    ROUND_DATA.save(storage, round_id, round_data)?;
+    ROUND_IDS_BY_HEIGHT.save(storage, env.block.height, &round_id)?;
+    ROUND_IDS_BY_TIME.save(storage, env.block.time.seconds(), &round_id)?;
+
+    // update the latest round id in the price feed info
+    price_feed_info.latest_round = round_id;
+    PRICE_FEED_INFO.save(storage, &price_feed_info)?;
+    Ok(round_id)
+}
+
+// the bookkeeping of a finalized round, the final answer is none if the round is rejected
+fn settle_round(
+    storage: &mut dyn Storage,
+    round_data: &RoundData,
+    config: &Config,
+    final_answer: Option<u64>,
+) -> StdResult<()> {
+    update_feeder_stats(storage, round_data, config, final_answer)?;
+    if final_answer.is_some() {
+        allocate_rewards(storage, round_data, config)?;
+    }
+    slash_outliers(storage, round_data, config)
+}
+
+// count the participation of every active feeder in a finalized round,
+// and the deviation of their answers from the final answer if the round is answered
+fn update_feeder_stats(
+    storage: &mut dyn Storage,
+    round_data: &RoundData,
+    config: &Config,
+    final_answer: Option<u64>,
+) -> StdResult<()> {
+    let mut active_feeders = vec![];
+    for item in FEEDERS.range(storage, None, None, cosmwasm_std::Order::Ascending) {
+        let (address, feeder) = item?;
+        if config.is_active_feeder(&feeder) {
+            active_feeders.push(address);
+        }
+    }
+
+    for address in active_feeders {
+        let mut stats = FEEDER_STATS
+            .may_load(storage, address.clone())?
+            .unwrap_or_default();
+        match round_data.answers.iter().find(|a| a.provider == address) {
+            Some(answer) => {
+                stats.rounds_participated += 1;
+                if let Some(final_answer) = final_answer {
+                    let deviation = if final_answer == 0 {
+                        Decimal::zero()
+                    } else {
+                        Decimal::from_ratio(answer.value.abs_diff(final_answer), final_answer)
+                    };
+                    stats.rounds_answered += 1;
+                    stats.total_deviation += deviation;
+                    stats.max_deviation = stats.max_deviation.max(deviation);
+                    stats.last_deviation = Some(deviation);
                 }
             }
+            None => stats.rounds_missed += 1,
         }
+        FEEDER_STATS.save(storage, address, &stats)?;
+    }
+    Ok(())
+}
 
-        // The lastest round is expired, so create new round data
-        // create new round data
-        let round_data = RoundData {
-            started_at_height: env.block.height,
-            status: crate::state::RoundDataStatus::Pending,
-            answers: vec![Answer {
-                provider: info.sender,
-                value: answer,
-                updated_at_height: env.block.height,
-            }],
-            answered_at_height: 0,
-        };
+// allocate the reward from the pool to each feeder whose answer is counted in an answered round,
+// the feeders are not rewarded anymore when the pool runs out
+fn allocate_rewards(
+    storage: &mut dyn Storage,
+    round_data: &RoundData,
+    config: &Config,
+) -> StdResult<()> {
+    let reward = match &config.reward {
+        Some(reward) if !reward.amount.is_zero() => reward.amount,
+        _ => return Ok(()),
+    };
 
-        // save the round data
-        ROUND_DATA.save(deps.storage, env.block.height, &round_data)?;
+    let mut pool = REWARD_POOL.may_load(storage)?.unwrap_or_default();
+    for provider in round_data.accepted_providers(config.deviation_band) {
+        if pool < reward {
+            break;
+        }
+        pool -= reward;
+        REWARDS.update(storage, provider, |rewards| -> StdResult<_> {
+            Ok(rewards.unwrap_or_default() + reward)
+        })?;
+    }
+    REWARD_POOL.save(storage, &pool)
+}
 
-        // update the latest round id in the price feed info
-        let mut price_feed_info = PRICE_FEED_INFO.load(deps.storage)?;
-        price_feed_info.latest_round = env.block.height;
-        PRICE_FEED_INFO.save(deps.storage, &price_feed_info)?;
+// slash the bonds of the feeders whose answers are outside the accepted band,
+// the slashed coins go to the reward pool
+fn slash_outliers(
+    storage: &mut dyn Storage,
+    round_data: &RoundData,
+    config: &Config,
+) -> StdResult<()> {
+    let slash_fraction = match &config.staking {
+        Some(staking) if !staking.slash_fraction.is_zero() => staking.slash_fraction,
+        _ => return Ok(()),
+    };
 
-        Ok(res.add_attributes([
-            ("method", "update_round_data"),
-            ("round_id", env.block.height.to_string().as_str()),
-            ("answer", answer.to_string().as_str()),
-        ]))
+    let accepted_providers = round_data.accepted_providers(config.deviation_band);
+    let mut slashed = Uint128::zero();
+    for answer in &round_data.answers {
+        if accepted_providers.contains(&answer.provider) {
+            continue;
+        }
+
+        // the coins which are being unbonded are slashed too
+        if let Some(mut feeder) = FEEDERS.may_load(storage, answer.provider.clone())? {
+            let amount = feeder.bond * slash_fraction;
+            feeder.bond -= amount;
+            slashed += amount;
+            FEEDERS.save(storage, answer.provider.clone(), &feeder)?;
+        }
+        if let Some(mut unbonding) = UNBONDINGS.may_load(storage, answer.provider.clone())? {
+            let amount = unbonding.amount * slash_fraction;
+            unbonding.amount -= amount;
+            slashed += amount;
+            UNBONDINGS.save(storage, answer.provider.clone(), &unbonding)?;
+        }
+    }
+
+    if !slashed.is_zero() {
+        let pool = REWARD_POOL.may_load(storage)?.unwrap_or_default();
+        REWARD_POOL.save(storage, &(pool + slashed))?;
+    }
+    Ok(())
+}
+
+// the number of active feeders
+fn number_feeders(storage: &dyn Storage) -> StdResult<usize> {
+    let config = CONFIG.load(storage)?;
+    let mut number_feeders = 0;
+    for item in FEEDERS.range(storage, None, None, cosmwasm_std::Order::Ascending) {
+        if config.is_active_feeder(&item?.1) {
+            number_feeders += 1;
+        }
     }
+    Ok(number_feeders)
+}
+
+// the messages to push the answer of a round to every price feed contract
+fn update_price_feed_msgs(storage: &dyn Storage, answer: u64) -> StdResult<Vec<WasmMsg>> {
+    let price_feed_info = PRICE_FEED_INFO.load(storage)?;
+    let msg = to_binary(&PriceFeedExecuteMsg::UpdateRoundData { answer })?;
+    Ok(price_feed_info
+        .price_feeds
+        .iter()
+        .map(|price_feed| WasmMsg::Execute {
+            contract_addr: price_feed.to_string(),
+            msg: msg.clone(),
+            funds: vec![],
+        })
+        .collect())
 }
 
 pub fn query_lastest_round_data(deps: Deps, _env: Env) -> StdResult<RoundData> {
@@ -259,20 +1464,128 @@ pub fn query_lastest_round_data(deps: Deps, _env: Env) -> StdResult<RoundData> {
 }
 
 pub fn query_round_data(deps: Deps, round_id: u64) -> StdResult<RoundData> {
-    let min: Option<Bound<u64>> = Some(Bound::inclusive(round_id - MAX_DIFF_DURRATION * 13)); // query data in previous 1 hour 5 minutes
-    let max: Option<Bound<u64>> = Some(Bound::inclusive(round_id));
     // load the round data from the round data map
-    let binding = ROUND_DATA
-        .range(deps.storage, min, max, cosmwasm_std::Order::Ascending)
-        .collect::<StdResult<Vec<_>>>()?;
-    let round_data = binding.last().unwrap();
+    ROUND_DATA.load(deps.storage, round_id)
+}
 
-    let res = RoundData {
-        started_at_height: round_data.1.started_at_height,
-        status: round_data.1.status.clone(),
-        answers: round_data.1.answers.clone(),
-        answered_at_height: round_data.1.answered_at_height,
+pub fn query_rounds(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+    order: Option<OrderBy>,
+) -> StdResult<Vec<RoundResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let order: cosmwasm_std::Order = order.unwrap_or(OrderBy::Ascending).into();
+
+    // the page starts after `start_after` in the direction of the order
+    let start_after = start_after.map(Bound::exclusive);
+    let (min, max) = match order {
+        cosmwasm_std::Order::Ascending => (start_after, None),
+        cosmwasm_std::Order::Descending => (None, start_after),
     };
-    // return the round data
-    Ok(res)
+
+    ROUND_DATA
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|item| {
+            let (round_id, round_data) = item?;
+            Ok(RoundResponse {
+                round_id,
+                round_data,
+            })
+        })
+        .collect()
+}
+
+// find the latest round started at or before the key of the index
+pub fn query_round_at(
+    deps: Deps,
+    index: &Map<u64, u64>,
+    key: u64,
+) -> StdResult<Option<RoundResponse>> {
+    let round_id = match index
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::inclusive(key)),
+            cosmwasm_std::Order::Descending,
+        )
+        .next()
+    {
+        Some(item) => item?.1,
+        None => return Ok(None),
+    };
+
+    Ok(ROUND_DATA
+        .may_load(deps.storage, round_id)?
+        .map(|round_data| RoundResponse {
+            round_id,
+            round_data,
+        }))
+}
+
+pub fn query_feeder(deps: Deps, address: String) -> StdResult<FeederResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let feeder = FEEDERS.load(deps.storage, address.clone())?;
+    Ok(feeder_response(address, feeder))
+}
+
+pub fn query_list_feeders(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<FeederResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = match start_after {
+        Some(start_after) => Some(Bound::exclusive(deps.api.addr_validate(&start_after)?)),
+        None => None,
+    };
+
+    FEEDERS
+        .range(deps.storage, start, None, cosmwasm_std::Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (address, feeder) = item?;
+            Ok(feeder_response(address, feeder))
+        })
+        .collect()
+}
+
+fn feeder_response(address: Addr, feeder: Feeder) -> FeederResponse {
+    FeederResponse {
+        address: address.to_string(),
+        moniker: feeder.moniker,
+        pubkey: feeder.pubkey,
+        joined_height: feeder.joined_height,
+        active: feeder.active,
+        last_submission_height: feeder.last_submission_height,
+        bond: feeder.bond,
+    }
+}
+
+pub fn query_feeder_stats(deps: Deps, address: String) -> StdResult<FeederStatsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let stats: FeederStats = FEEDER_STATS
+        .may_load(deps.storage, address.clone())?
+        .unwrap_or_default();
+
+    let average_deviation = if stats.rounds_answered == 0 {
+        Decimal::zero()
+    } else {
+        stats.total_deviation / Decimal::from_ratio(stats.rounds_answered, 1u64)
+    };
+
+    Ok(FeederStatsResponse {
+        address: address.to_string(),
+        rounds_participated: stats.rounds_participated,
+        rounds_missed: stats.rounds_missed,
+        average_deviation,
+        max_deviation: stats.max_deviation,
+        last_deviation: stats.last_deviation,
+    })
+}
+
+pub fn query_pending_rewards(deps: Deps, address: String) -> StdResult<Uint128> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(REWARDS.may_load(deps.storage, address)?.unwrap_or_default())
 }
@@ -1,4 +1,6 @@
 use cosmwasm_std::StdError;
+use cw_utils::PaymentError;
+use ownership::OwnershipError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,6 +8,15 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("{0}")]
+    SemVer(#[from] semver::Error),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -18,4 +29,70 @@ pub enum ContractError {
 
     #[error("Round Ended")]
     RoundEnded {},
+
+    #[error("Invalid Config")]
+    InvalidConfig {},
+
+    #[error("Invalid Pubkey")]
+    InvalidPubkey {},
+
+    #[error("Invalid Report")]
+    InvalidReport {},
+
+    #[error("Invalid Signature")]
+    InvalidSignature {},
+
+    #[error("Nothing To Claim")]
+    NothingToClaim {},
+
+    #[error("Nothing To Unbond")]
+    NothingToUnbond {},
+
+    #[error("Unbonding Not Finished")]
+    UnbondingNotFinished {},
+
+    #[error("Commit Reveal Required")]
+    CommitRevealRequired {},
+
+    #[error("Commit Reveal Disabled")]
+    CommitRevealDisabled {},
+
+    #[error("Invalid Phase")]
+    InvalidPhase {},
+
+    #[error("Invalid Reveal")]
+    InvalidReveal {},
+
+    #[error("Round In Progress")]
+    RoundInProgress {},
+
+    #[error("Round Not Finalizable")]
+    RoundNotFinalizable {},
+
+    #[error("Force Round Data Disabled")]
+    ForceRoundDataDisabled {},
+
+    #[error("Invalid Reason")]
+    InvalidReason {},
+
+    #[error("Price Feed Already Exists")]
+    PriceFeedAlreadyExists {},
+
+    #[error("Price Feed Not Found")]
+    PriceFeedNotFound {},
+
+    #[error("Cannot Migrate from {contract} {version}")]
+    InvalidMigration { contract: String, version: String },
+
+    #[error("Cannot Migrate from the Newer Version {version}")]
+    CannotDowngrade { version: String },
+
+    #[error("Operation Paused")]
+    Paused {},
+
+    #[error("Operation Already Paused")]
+    AlreadyPaused {},
+
+    #[error("Operation Not Paused")]
+    NotPaused {},
 }
@@ -0,0 +1,136 @@
+use cosmwasm_std::{Addr, Decimal, DepsMut, Env, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::Bound;
+use ownership::initialize_owner;
+
+use crate::contract::DEFAULT_ROUND_DURATION;
+use crate::state::{
+    AggregationStrategy, Config, Feeder, PriceFeedInfo, RoundIndexBackfill, CONFIG, FEEDERS,
+    PRICE_FEED_INFO, REWARD_POOL, ROUND_DATA, ROUND_IDS_BY_HEIGHT, ROUND_INDEX_BACKFILL,
+};
+
+// the state layout of v0.1, which is replaced by the current one
+pub mod v0_1 {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::Addr;
+    use cw_storage_plus::{Item, Map};
+
+    #[cw_serde]
+    pub struct Config {
+        pub owner: Addr,
+    }
+
+    #[cw_serde]
+    pub struct PriceFeedInfo {
+        pub price_feed: Addr,
+        pub latest_round: u64,
+    }
+
+    pub const CONFIG: Item<Config> = Item::new("config");
+    pub const PRICE_FEED_INFO: Item<PriceFeedInfo> = Item::new("price_feed_info");
+    // the status of each feeder
+    pub const FEEDERS: Map<Addr, bool> = Map::new("feeders");
+}
+
+// migrate the state of v0.1 to the current layout
+pub fn migrate_from_v0_1(deps: DepsMut, env: &Env) -> StdResult<()> {
+    // the owner moves to the ownership and the new settings take the default values
+    let config = v0_1::CONFIG.load(deps.storage)?;
+    initialize_owner(deps.storage, deps.api, config.owner.as_str())?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            aggregation: AggregationStrategy::Median {},
+            deviation_band: Decimal::percent(10),
+            quorum: Decimal::from_ratio(2u128, 3u128),
+            round_duration: DEFAULT_ROUND_DURATION,
+            reward: None,
+            staking: None,
+            commit_duration: None,
+            keeper_tip: None,
+            guardian: None,
+            force_disabled: false,
+        },
+    )?;
+
+    let price_feed_info = v0_1::PRICE_FEED_INFO.load(deps.storage)?;
+    PRICE_FEED_INFO.save(
+        deps.storage,
+        &PriceFeedInfo {
+            price_feeds: vec![price_feed_info.price_feed],
+            latest_round: price_feed_info.latest_round,
+        },
+    )?;
+    REWARD_POOL.save(deps.storage, &Uint128::zero())?;
+
+    // v0.1 has no round indexes, the rounds are indexed in batches by `backfill_round_indexes`
+    ROUND_INDEX_BACKFILL.save(
+        deps.storage,
+        &RoundIndexBackfill {
+            start_after: None,
+            last_round: price_feed_info.latest_round,
+        },
+    )?;
+
+    // the feeders of v0.1 only have a status, they join at the migration height
+    let feeders = v0_1::FEEDERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(Addr, bool)>>>()?;
+    for (address, active) in feeders {
+        FEEDERS.save(
+            deps.storage,
+            address,
+            &Feeder {
+                moniker: String::new(),
+                pubkey: None,
+                joined_height: env.block.height,
+                active,
+                last_submission_height: None,
+                bond: Uint128::zero(),
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+// index at most `limit` of the rounds of v0.1 by block height, returns the number of indexed
+// rounds and whether all of them are indexed, the rounds of v0.1 do not store their start time
+// so they are not indexed by time
+pub fn backfill_round_indexes(storage: &mut dyn Storage, limit: u32) -> StdResult<(u32, bool)> {
+    let mut backfill = match ROUND_INDEX_BACKFILL.may_load(storage)? {
+        Some(backfill) => backfill,
+        None => return Ok((0, true)),
+    };
+
+    let rounds = ROUND_DATA
+        .range(
+            storage,
+            backfill.start_after.map(Bound::exclusive),
+            Some(Bound::inclusive(backfill.last_round)),
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // an index entry which already exists belongs to a later round and is kept
+    for (round_id, round_data) in &rounds {
+        ROUND_IDS_BY_HEIGHT.update(
+            storage,
+            round_data.started_at_height,
+            |indexed| -> StdResult<_> {
+                Ok(indexed.map_or(*round_id, |indexed| indexed.max(*round_id)))
+            },
+        )?;
+    }
+
+    // all the rounds are indexed once a batch is not full
+    let finished = rounds.len() < limit as usize;
+    if finished {
+        ROUND_INDEX_BACKFILL.remove(storage);
+    } else {
+        backfill.start_after = rounds.last().map(|(round_id, _)| *round_id);
+        ROUND_INDEX_BACKFILL.save(storage, &backfill)?;
+    }
+
+    Ok((rounds.len() as u32, finished))
+}
@@ -1,24 +1,145 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Timestamp, Uint128};
+use cw_utils::Expiration;
+use ownership::Ownership;
 
-use crate::state::RoundData;
+pub use price_feed::msg::OrderBy;
+
+use crate::state::{
+    AggregationStrategy, Config, ManualRound, Operation, RoundData, StakingConfig, Unbonding,
+};
 
 /// Message type for `instantiate` entry_point
 #[cw_serde]
 pub struct InstantiateMsg {
     pub price_feed: String,
     pub decimals: u8,
+    pub aggregation: Option<AggregationStrategy>,
+    pub deviation_band: Option<Decimal>,
+    pub quorum: Option<Decimal>,
+    pub round_duration: Option<u64>,
+    pub reward: Option<Coin>,
+    pub staking: Option<StakingConfig>,
+    pub commit_duration: Option<u64>,
+    pub keeper_tip: Option<Coin>,
 }
 
 /// Message type for `execute` entry_point
 #[cw_serde]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
-    UpdatePriceFeeder { price_feeder: String, status: bool },
-    ProvideRoundData { answer: u64 },
+    UpdatePriceFeeder {
+        price_feeder: String,
+        status: bool,
+        moniker: Option<String>,
+    },
+    ProvideRoundData {
+        answer: u64,
+    },
+    // commit the hash of the answer in the commit phase of the round
+    CommitRoundData {
+        commitment: Binary,
+    },
+    // reveal the committed answer after the commit phase of the round
+    RevealRoundData {
+        answer: u64,
+        salt: String,
+    },
+    UpdateConfig {
+        aggregation: Option<AggregationStrategy>,
+        deviation_band: Option<Decimal>,
+        quorum: Option<Decimal>,
+        round_duration: Option<u64>,
+        reward: Option<Coin>,
+        staking: Option<StakingConfig>,
+        // 0 turns the commit-reveal mode off
+        commit_duration: Option<u64>,
+        keeper_tip: Option<Coin>,
+    },
+    RegisterPubkey {
+        pubkey: Binary,
+    },
+    SubmitReport {
+        report: Report,
+        signatures: Vec<ReportSignature>,
+    },
+    // the owner funds the reward pool with the reward coins
+    FundRewards {},
+    // the feeder claims all of its allocated rewards
+    ClaimRewards {},
+    // bond the coins required to provide answers, the owner still enables the feeder
+    Bond {
+        moniker: Option<String>,
+    },
+    // start unbonding the whole bond, the feeder cannot provide answers until it bonds again
+    Unbond {},
+    // withdraw the unbonded coins after the unbonding period
+    WithdrawUnbonded {},
+    // anyone can finalize the latest round once it expires or its answers reach the quorum
+    FinalizeRound {},
+    UpdateGuardian {
+        guardian: Option<String>,
+    },
+    // set the round data in an emergency, only the owner or the guardian can do it,
+    // the pending round which is still live is rejected
+    ForceRoundData {
+        answer: u64,
+        reason: String,
+    },
+    // disable the ForceRoundData permanently
+    DisableForceRoundData {},
+    // pause an operation in an emergency, only the owner or the guardian can do it
+    Pause {
+        operation: Operation,
+    },
+    // resume a paused operation, only the owner can do it
+    Unpause {
+        operation: Operation,
+    },
+    AddPriceFeed {
+        price_feed: String,
+    },
+    RemovePriceFeed {
+        price_feed: String,
+    },
+    // propose a new owner, who must accept the ownership before the expiry
+    ProposeNewOwner {
+        new_owner: String,
+        expiry: Option<Expiration>,
+    },
+    AcceptOwnership {},
+    // give up the ownership permanently
+    RenounceOwnership {},
+}
+
+// the answers of the feeders for a round, collected off-chain and signed by each of them
+#[cw_serde]
+pub struct Report {
+    // the round id, must be the next round id after the latest round
+    pub round_id: u64,
+    pub observations: Vec<Observation>,
+}
+
+#[cw_serde]
+pub struct Observation {
+    pub feeder: String,
+    pub value: u64,
+}
+
+// the secp256k1 signature of a feeder over the hash of the report
+#[cw_serde]
+pub struct ReportSignature {
+    pub feeder: String,
+    pub signature: Binary,
 }
 
 /// Message type for `migrate` entry_point
 #[cw_serde]
-pub enum MigrateMsg {}
+pub struct MigrateMsg {
+    /// the max number of rounds of v0.1 indexed by this migration, 1000 by default,
+    /// the migration is repeated with the same code until all of them are indexed
+    pub limit: Option<u32>,
+}
 
 /// Message type for `query` entry_point
 #[cw_serde]
@@ -28,4 +149,86 @@ pub enum QueryMsg {
     LastestRoundData {},
     #[returns(RoundData)]
     RoundData { round_id: u64 },
+    /// a page of the rounds in the history, in ascending order of round id by default
+    #[returns(Vec<RoundResponse>)]
+    Rounds {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<OrderBy>,
+    },
+    /// the latest round started at or before the block height, none if there is no such round
+    #[returns(Option<RoundResponse>)]
+    RoundAtHeight { height: u64 },
+    /// the latest round started at or before the time, none if there is no such round
+    #[returns(Option<RoundResponse>)]
+    RoundAtTime { time: Timestamp },
+    #[returns(Config)]
+    Config {},
+    /// the names of the paused operations
+    #[returns(Vec<String>)]
+    PausedOperations {},
+    /// the price feed contracts which receive the answers
+    #[returns(Vec<Addr>)]
+    PriceFeeds {},
+    /// the reason and the sender of a forced round, none if the round is not forced
+    #[returns(Option<ManualRound>)]
+    ManualRound { round_id: u64 },
+    /// the hash of the report which the feeders must sign
+    #[returns(Binary)]
+    ReportHash { report: Report },
+    /// the commitment of the answer which the feeder must commit
+    #[returns(Binary)]
+    CommitmentHash {
+        feeder: String,
+        answer: u64,
+        salt: String,
+    },
+    #[returns(FeederResponse)]
+    Feeder { address: String },
+    #[returns(Vec<FeederResponse>)]
+    ListFeeders {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(FeederStatsResponse)]
+    FeederStats { address: String },
+    /// the amount of reward coins which are not allocated yet
+    #[returns(Uint128)]
+    RewardPool {},
+    /// the rewards allocated to the feeder and not claimed yet
+    #[returns(Uint128)]
+    PendingRewards { address: String },
+    /// the coins which are being unbonded by the feeder
+    #[returns(Option<Unbonding>)]
+    Unbonding { address: String },
+    #[returns(Ownership)]
+    Ownership {},
+}
+
+#[cw_serde]
+pub struct RoundResponse {
+    pub round_id: u64,
+    pub round_data: RoundData,
+}
+
+#[cw_serde]
+pub struct FeederResponse {
+    pub address: String,
+    pub moniker: String,
+    pub pubkey: Option<Binary>,
+    pub joined_height: u64,
+    pub active: bool,
+    pub last_submission_height: Option<u64>,
+    pub bond: Uint128,
+}
+
+#[cw_serde]
+pub struct FeederStatsResponse {
+    pub address: String,
+    pub rounds_participated: u64,
+    pub rounds_missed: u64,
+    // the average and max relative deviation of the answers from the final answers
+    pub average_deviation: Decimal,
+    pub max_deviation: Decimal,
+    pub last_deviation: Option<Decimal>,
 }
@@ -1,19 +1,205 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Empty, Timestamp, Uint128};
 use cw_storage_plus::{Item, Map};
 
 // the config of the price feed
 #[cw_serde]
 pub struct Config {
-    pub owner: Addr,
+    // the way the accepted answers of a round are aggregated
+    pub aggregation: AggregationStrategy,
+    // the answers which are not in the band of +/- deviation_band around the median are discarded
+    pub deviation_band: Decimal,
+    // the round is answered when the ratio of accepted answers to feeders is greater than the quorum
+    pub quorum: Decimal,
+    // the number of blocks a round stays open for new answers
+    pub round_duration: u64,
+    // the reward paid from the reward pool for each answer counted in an answered round
+    pub reward: Option<Coin>,
+    // the feeders must bond coins to join when the staking is set
+    pub staking: Option<StakingConfig>,
+    // the number of blocks of the commit phase at the start of each round,
+    // the feeders commit the hashes of their answers before revealing them when it is set
+    pub commit_duration: Option<u64>,
+    // the tip paid from the reward pool to whoever finalizes a round
+    pub keeper_tip: Option<Coin>,
+    // the guardian can force the round data and pause the operations in an emergency, like the owner
+    pub guardian: Option<Addr>,
+    // the round data cannot be forced anymore once it is disabled
+    pub force_disabled: bool,
+}
+
+impl Config {
+    pub fn is_valid(&self) -> bool {
+        self.aggregation.is_valid()
+            && self.deviation_band <= Decimal::one()
+            && self.quorum < Decimal::one()
+            && self.round_duration > 0
+            && self.staking.as_ref().map_or(true, |s| s.is_valid())
+            && self
+                .commit_duration
+                .map_or(true, |d| d > 0 && d < self.round_duration)
+            // the slashed bonds go to the reward pool, so they must be in the same denom
+            && match (&self.reward, &self.staking) {
+                (Some(reward), Some(staking)) => reward.denom == staking.min_bond.denom,
+                _ => true,
+            }
+            // the keeper tip is paid from the reward pool
+            && self
+                .keeper_tip
+                .as_ref()
+                .map_or(true, |tip| Some(tip.denom.as_str()) == self.pool_denom())
+    }
+
+    // the denom of the reward pool, which is funded with the rewards and the slashed bonds
+    pub fn pool_denom(&self) -> Option<&str> {
+        match (&self.reward, &self.staking) {
+            (Some(reward), _) => Some(&reward.denom),
+            (None, Some(staking)) => Some(&staking.min_bond.denom),
+            (None, None) => None,
+        }
+    }
+
+    // a feeder is active when it is enabled and bonds enough coins if the staking is set
+    pub fn is_active_feeder(&self, feeder: &Feeder) -> bool {
+        feeder.active
+            && self
+                .staking
+                .as_ref()
+                .map_or(true, |s| feeder.bond >= s.min_bond.amount)
+    }
+
+    pub fn reaches_quorum(&self, number_answers: usize, number_feeders: usize) -> bool {
+        number_feeders > 0
+            && Decimal::from_ratio(number_answers as u128, number_feeders as u128) > self.quorum
+    }
+}
+
+// the operations which can be paused independently
+#[cw_serde]
+pub enum Operation {
+    // the answers of the feeders, directly, by the commit-reveal or by the signed reports
+    ProvideRoundData,
+}
+
+impl Operation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operation::ProvideRoundData => "provide_round_data",
+        }
+    }
+}
+
+#[cw_serde]
+pub struct StakingConfig {
+    // the min amount of coins a feeder must bond to be active
+    pub min_bond: Coin,
+    // the number of seconds the unbonded coins are locked before they can be withdrawn
+    pub unbonding_period: u64,
+    // the fraction of the bond which is slashed for an answer outside the accepted band
+    pub slash_fraction: Decimal,
+}
+
+impl StakingConfig {
+    pub fn is_valid(&self) -> bool {
+        self.slash_fraction <= Decimal::one()
+    }
+}
+
+#[cw_serde]
+pub enum AggregationStrategy {
+    Mean {},
+    Median {},
+    // the trim_percent of the lowest and the highest answers are cut before taking the mean
+    TrimmedMean { trim_percent: u8 },
+}
+
+impl AggregationStrategy {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            AggregationStrategy::TrimmedMean { trim_percent } => *trim_percent < 50,
+            _ => true,
+        }
+    }
+
+    // aggregate the values, which must be sorted in ascending order
+    pub fn aggregate(&self, sorted_values: &[u64]) -> u64 {
+        if sorted_values.is_empty() {
+            return 0;
+        }
+        match self {
+            AggregationStrategy::Mean {} => mean(sorted_values),
+            AggregationStrategy::Median {} => median(sorted_values),
+            AggregationStrategy::TrimmedMean { trim_percent } => {
+                let trimmed = sorted_values.len() * (*trim_percent as usize) / 100;
+                mean(&sorted_values[trimmed..sorted_values.len() - trimmed])
+            }
+        }
+    }
+}
+
+fn mean(values: &[u64]) -> u64 {
+    let sum: u128 = values.iter().map(|v| *v as u128).sum();
+    (sum / values.len() as u128) as u64
+}
+
+// the values must be sorted in ascending order
+fn median(sorted_values: &[u64]) -> u64 {
+    let middle = sorted_values.len() / 2;
+    if sorted_values.len() % 2 == 0 {
+        mean(&sorted_values[middle - 1..=middle])
+    } else {
+        sorted_values[middle]
+    }
 }
 
 #[cw_serde]
 pub struct PriceFeedInfo {
-    pub price_feed: Addr,
+    // the price feed contracts which receive the answers
+    pub price_feeds: Vec<Addr>,
     pub latest_round: u64,
 }
 
+// the information of a feeder, inactive feeders are kept for auditing
+#[cw_serde]
+pub struct Feeder {
+    pub moniker: String,
+    // the secp256k1 public key the feeder uses to sign the reports
+    pub pubkey: Option<Binary>,
+    pub joined_height: u64,
+    pub active: bool,
+    pub last_submission_height: Option<u64>,
+    // the amount of coins bonded by the feeder
+    pub bond: Uint128,
+}
+
+// the coins which are being unbonded by a feeder
+#[cw_serde]
+pub struct Unbonding {
+    pub amount: Uint128,
+    pub release_at: Timestamp,
+}
+
+// the audit trail of a round forced by the owner or the guardian
+#[cw_serde]
+pub struct ManualRound {
+    pub forced_by: Addr,
+    pub reason: String,
+}
+
+// the performance of a feeder in the finalized rounds
+#[cw_serde]
+#[derive(Default)]
+pub struct FeederStats {
+    pub rounds_participated: u64,
+    pub rounds_missed: u64,
+    // the number of answered rounds the feeder participated in, used to average the deviation
+    pub rounds_answered: u64,
+    // the sum of the relative deviations of the answers from the final answers
+    pub total_deviation: Decimal,
+    pub max_deviation: Decimal,
+    pub last_deviation: Option<Decimal>,
+}
+
 #[cw_serde]
 pub enum RoundDataStatus {
     Pending,
@@ -42,14 +228,47 @@ impl RoundData {
         self.answers.iter().any(|a| a.provider == provider)
     }
 
-    pub fn current_answer(&self) -> u64 {
-        // get the average of all answers
-        let sum: u64 = self.answers.iter().map(|a| a.value).sum();
-        if sum == 0 {
-            0
-        } else {
-            sum / self.answers.len() as u64
+    // the answers in the band of +/- deviation_band around the median
+    fn answers_in_band(&self, deviation_band: Decimal) -> Vec<&Answer> {
+        if self.answers.is_empty() {
+            return vec![];
         }
+        let mut values: Vec<u64> = self.answers.iter().map(|a| a.value).collect();
+        values.sort_unstable();
+
+        let median = Uint128::from(median(&values));
+        let lower = median * (Decimal::one() - deviation_band);
+        let upper = median * (Decimal::one() + deviation_band);
+        self.answers
+            .iter()
+            .filter(|a| Uint128::from(a.value) >= lower && Uint128::from(a.value) <= upper)
+            .collect()
+    }
+
+    // the sorted values of the answers in the band of +/- deviation_band around the median
+    pub fn accepted_answers(&self, deviation_band: Decimal) -> Vec<u64> {
+        let mut values: Vec<u64> = self
+            .answers_in_band(deviation_band)
+            .iter()
+            .map(|a| a.value)
+            .collect();
+        values.sort_unstable();
+        values
+    }
+
+    // the providers of the answers in the band of +/- deviation_band around the median
+    pub fn accepted_providers(&self, deviation_band: Decimal) -> Vec<Addr> {
+        self.answers_in_band(deviation_band)
+            .iter()
+            .map(|a| a.provider.clone())
+            .collect()
+    }
+
+    pub fn current_answer(&self, config: &Config) -> u64 {
+        // aggregate the answers which are not outliers
+        config
+            .aggregation
+            .aggregate(&self.accepted_answers(config.deviation_band))
     }
 
     pub fn current_number_answeres(&self) -> usize {
@@ -57,11 +276,60 @@ impl RoundData {
     }
 }
 
+// the progress of indexing the rounds of v0.1, which is done in batches by the migrations
+#[cw_serde]
+pub struct RoundIndexBackfill {
+    // the last round which is indexed
+    pub start_after: Option<u64>,
+    // the latest round of v0.1, the rounds after it are indexed when they are saved
+    pub last_round: u64,
+}
+
 // the config data
 pub const CONFIG: Item<Config> = Item::new("config");
 // the round data is stored in the map with the round id as the key
 pub const ROUND_DATA: Map<u64, RoundData> = Map::new("round_data");
+// the index from the block height to the latest round started at that height
+pub const ROUND_IDS_BY_HEIGHT: Map<u64, u64> = Map::new("round_ids_by_height");
+// the index from the block time (in seconds) to the latest round started at that time
+pub const ROUND_IDS_BY_TIME: Map<u64, u64> = Map::new("round_ids_by_time");
 // the list of feeders
-pub const FEEDERS: Map<Addr, bool> = Map::new("feeders");
+pub const FEEDERS: Map<Addr, Feeder> = Map::new("feeders");
+// the statistics of each feeder
+pub const FEEDER_STATS: Map<Addr, FeederStats> = Map::new("feeder_stats");
+// the amount of reward coins which are not allocated to the feeders yet
+pub const REWARD_POOL: Item<Uint128> = Item::new("reward_pool");
+// the rewards allocated to each feeder and not claimed yet
+pub const REWARDS: Map<Addr, Uint128> = Map::new("rewards");
+// the rounds forced by the owner or the guardian
+pub const MANUAL_ROUNDS: Map<u64, ManualRound> = Map::new("manual_rounds");
+// the commitments of the feeders in each round,
+// which are removed when they are revealed or the round is finalized
+pub const COMMITS: Map<(u64, &Addr), Binary> = Map::new("commits");
+// the coins which are being unbonded by each feeder
+pub const UNBONDINGS: Map<Addr, Unbonding> = Map::new("unbondings");
 // the price feed info
 pub const PRICE_FEED_INFO: Item<PriceFeedInfo> = Item::new("price_feed_info");
+// the paused operations, keyed by the operation name
+pub const PAUSED_OPERATIONS: Map<&str, Empty> = Map::new("paused_operations");
+// the rounds of v0.1 which are not indexed yet, removed once all of them are indexed
+pub const ROUND_INDEX_BACKFILL: Item<RoundIndexBackfill> = Item::new("round_index_backfill");
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn aggregate() {
+        let values = vec![1, 2, 3, 4, 100];
+
+        assert_eq!(AggregationStrategy::Mean {}.aggregate(&values), 22);
+        assert_eq!(AggregationStrategy::Median {}.aggregate(&values), 3);
+        assert_eq!(AggregationStrategy::Median {}.aggregate(&values[..4]), 2);
+        assert_eq!(
+            AggregationStrategy::TrimmedMean { trim_percent: 20 }.aggregate(&values),
+            3
+        );
+        assert!(!AggregationStrategy::TrimmedMean { trim_percent: 50 }.is_valid());
+    }
+}
@@ -1,11 +1,16 @@
 #[cfg(test)]
 pub mod env {
-    use cosmwasm_std::{Addr, BlockInfo, Coin, Empty, Uint128};
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{
+        Addr, BlockInfo, Coin, DepsMut, Empty, Env, MessageInfo, Response, StdResult, Uint128,
+    };
 
     use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
 
-    use crate::contract::{execute, instantiate, query};
+    use crate::contract::{execute, instantiate, migrate, query};
+    use crate::migrations::v0_1;
     use crate::msg::{ExecuteMsg, InstantiateMsg};
+    use crate::state::{Answer, RoundData, RoundDataStatus, ROUND_DATA};
     use price_feed::contract::{
         execute as PriceFeedExecute, instantiate as PriceFeedInstantiate, query as PriceFeedQuery,
     };
@@ -13,11 +18,16 @@ pub mod env {
     use price_feed::msg::{
         ExecuteMsg as PriceFeedExecuteMsg, InstantiateMsg as PriceFeedInstantiateMsg,
     };
+    use price_feed::state::Role;
 
     pub const ADMIN: &str = "aura1000000000000000000000000000000000admin";
     pub const USER1: &str = "aura1000000000000000000000000000000000user1";
     // pub const AURA: &str = "aura10000000000000000000000000000000000aura";
     pub const CONTROLLER: &str = "aura10000000000000000000000000000controller";
+    pub const FEEDER1: &str = "aura10000000000000000000000000000000feeder1";
+    pub const FEEDER2: &str = "aura10000000000000000000000000000000feeder2";
+    pub const FEEDER3: &str = "aura10000000000000000000000000000000feeder3";
+    pub const GUARDIAN: &str = "aura100000000000000000000000000000guardian";
 
     pub const NATIVE_DENOM: &str = "uaura";
     pub const NATIVE_BALANCE: u128 = 1_000_000_000_000u128;
@@ -61,10 +71,102 @@ pub mod env {
 
     // create price collector contract
     pub fn price_collector_contract_template() -> Box<dyn Contract<Empty>> {
-        let contract = ContractWrapper::new(execute, instantiate, query);
+        let contract = ContractWrapper::new(execute, instantiate, query).with_migrate(migrate);
         Box::new(contract)
     }
 
+    // the price feed and the version stored by the legacy price collector
+    #[cw_serde]
+    pub struct LegacyInstantiateMsg {
+        pub price_feed: String,
+        pub version: String,
+    }
+
+    // the price collector of v0.1 writes its state in the old layout,
+    // FEEDER1 is an active feeder and FEEDER2 is disabled
+    fn legacy_instantiate(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        msg: LegacyInstantiateMsg,
+    ) -> StdResult<Response> {
+        cw2::set_contract_version(deps.storage, "crates.io:price-collector", msg.version)?;
+        v0_1::CONFIG.save(deps.storage, &v0_1::Config { owner: info.sender })?;
+        v0_1::PRICE_FEED_INFO.save(
+            deps.storage,
+            &v0_1::PriceFeedInfo {
+                price_feed: deps.api.addr_validate(&msg.price_feed)?,
+                latest_round: 0,
+            },
+        )?;
+        v0_1::FEEDERS.save(deps.storage, Addr::unchecked(FEEDER1), &true)?;
+        v0_1::FEEDERS.save(deps.storage, Addr::unchecked(FEEDER2), &false)?;
+        Ok(Response::new())
+    }
+
+    #[cw_serde]
+    pub enum LegacyExecuteMsg {
+        ProvideRoundData { answer: u64 },
+    }
+
+    // the price collector of v0.1 keys the rounds by the block height they start at
+    fn legacy_execute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: LegacyExecuteMsg,
+    ) -> StdResult<Response> {
+        let LegacyExecuteMsg::ProvideRoundData { answer } = msg;
+        v0_1::PRICE_FEED_INFO.update(deps.storage, |mut price_feed_info| -> StdResult<_> {
+            price_feed_info.latest_round = env.block.height;
+            Ok(price_feed_info)
+        })?;
+        ROUND_DATA.save(
+            deps.storage,
+            env.block.height,
+            &RoundData {
+                started_at_height: env.block.height,
+                status: RoundDataStatus::Answered,
+                answers: vec![Answer {
+                    provider: info.sender,
+                    value: answer,
+                    updated_at_height: env.block.height,
+                }],
+                answered_at_height: env.block.height,
+            },
+        )?;
+        Ok(Response::new())
+    }
+
+    // instantiate a legacy price collector which stores the given version and can be migrated
+    // by the admin, returns its address and the code id of the current price collector
+    pub fn instantiate_legacy_price_collector(
+        app: &mut App,
+        price_feed: &str,
+        version: &str,
+    ) -> (Addr, u64) {
+        let legacy_contract_code_id = app.store_code(Box::new(ContractWrapper::new(
+            legacy_execute,
+            legacy_instantiate,
+            query,
+        )));
+        let price_collector_contract_code_id = app.store_code(price_collector_contract_template());
+        let legacy_contract_addr = app
+            .instantiate_contract(
+                legacy_contract_code_id,
+                Addr::unchecked(ADMIN),
+                &LegacyInstantiateMsg {
+                    price_feed: price_feed.to_string(),
+                    version: version.to_string(),
+                },
+                &[],
+                "test instantiate contract",
+                Some(ADMIN.to_string()),
+            )
+            .unwrap();
+        (legacy_contract_addr, price_collector_contract_code_id)
+    }
+
     pub fn instantiate_contracts() -> (App, Vec<ContractInfo>) {
         // Create a new app instance
         let mut app = mock_app();
@@ -84,6 +186,9 @@ pub mod env {
                     controller: CONTROLLER.to_string(),
                     decimals: 6,
                     description: "AURA / VND".to_string(),
+                    heartbeat: None,
+                    retention_policy: None,
+                    circuit_breaker: None,
                 },
                 &[],
                 "test instantiate contract",
@@ -105,6 +210,14 @@ pub mod env {
                 &InstantiateMsg {
                     price_feed: price_feed_contract_addr.to_string(),
                     decimals: 6,
+                    aggregation: None,
+                    deviation_band: None,
+                    quorum: None,
+                    round_duration: None,
+                    reward: None,
+                    staking: None,
+                    commit_duration: None,
+                    keeper_tip: None,
                 },
                 &[],
                 "test instantiate contract",
@@ -122,6 +235,7 @@ pub mod env {
         let exec_msg = ExecuteMsg::UpdatePriceFeeder {
             price_feeder: ADMIN.to_string(),
             status: true,
+            moniker: None,
         };
         let _res = app.execute_contract(
             Addr::unchecked(ADMIN),
@@ -130,9 +244,20 @@ pub mod env {
             &[],
         );
 
-        // update new controller for price feed contract
-        let exec_msg = PriceFeedExecuteMsg::UpdateController {
-            controller: price_collector_contract_addr.to_string(),
+        // the price collector replaces the controller as the writer of the price feed contract
+        let exec_msg = PriceFeedExecuteMsg::AddRole {
+            role: Role::Writer,
+            address: price_collector_contract_addr.to_string(),
+        };
+        let _res = app.execute_contract(
+            Addr::unchecked(ADMIN),
+            Addr::unchecked(price_feed_contract_addr.clone()),
+            &exec_msg,
+            &[],
+        );
+        let exec_msg = PriceFeedExecuteMsg::RemoveRole {
+            role: Role::Writer,
+            address: CONTROLLER.to_string(),
         };
         let _res = app.execute_contract(
             Addr::unchecked(ADMIN),
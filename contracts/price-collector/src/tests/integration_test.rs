@@ -1,12 +1,49 @@
 #![cfg(test)]
 mod tests {
-    use crate::contract::MAX_DIFF_DURRATION;
-    use crate::msg::ExecuteMsg;
-    use crate::tests::env_setup::env::{instantiate_contracts, ADMIN, USER1};
-    use cosmwasm_std::{Addr, Uint128};
-    use cw_multi_test::Executor;
+    use crate::contract::DEFAULT_ROUND_DURATION;
+    use crate::msg::{ExecuteMsg, QueryMsg};
+    use crate::state::{RoundData, RoundDataStatus};
+    use crate::tests::env_setup::env::{
+        instantiate_contracts, ADMIN, FEEDER1, FEEDER2, FEEDER3, USER1,
+    };
+    use cosmwasm_std::{Addr, StdResult, Uint128};
+    use cw_multi_test::{App, Executor};
     use price_feed::msg::{QueryMsg as PriceFeedQueryMsg, RoundDataResponse};
 
+    // the owner adds new feeders to the price collector contract
+    fn add_feeders(app: &mut App, price_collector_contract_addr: &str, feeders: &[&str]) {
+        for feeder in feeders {
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::UpdatePriceFeeder {
+                    price_feeder: feeder.to_string(),
+                    status: true,
+                    moniker: None,
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+        }
+    }
+
+    // each feeder provides its answer to the price collector contract
+    fn provide_answers(
+        app: &mut App,
+        price_collector_contract_addr: &str,
+        answers: &[(&str, u64)],
+    ) {
+        for (feeder, answer) in answers {
+            let res = app.execute_contract(
+                Addr::unchecked(*feeder),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::ProvideRoundData { answer: *answer },
+                &[],
+            );
+            assert!(res.is_ok());
+        }
+    }
+
     mod price_collector_testing {
         use cosmwasm_std::BlockInfo;
 
@@ -41,7 +78,7 @@ mod tests {
             // increase the block height to simulate the time passed
             app.set_block(BlockInfo {
                 time: app.block_info().time.plus_seconds(100001),
-                height: app.block_info().height + MAX_DIFF_DURRATION + 100,
+                height: app.block_info().height + DEFAULT_ROUND_DURATION + 100,
                 chain_id: app.block_info().chain_id,
             });
 
@@ -63,4 +100,2369 @@ mod tests {
             assert_eq!(res.answer, Uint128::from(100000u64));
         }
     }
+
+    mod aggregation {
+        use cosmwasm_std::BlockInfo;
+
+        use super::*;
+
+        #[test]
+        fn outliers_are_discarded_from_the_answer() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(
+                &mut app,
+                price_collector_contract_addr,
+                &[FEEDER1, FEEDER2, FEEDER3],
+            );
+
+            // 3 of 4 feeders answered, but 1000 is too far from the median
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(ADMIN, 100), (FEEDER1, 102), (FEEDER2, 1000)],
+            );
+            let res: RoundData = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.status, RoundDataStatus::Pending);
+
+            // the last feeder answers, so 3 answers are accepted and their median is pushed
+            provide_answers(&mut app, price_collector_contract_addr, &[(FEEDER3, 98)]);
+            let res: RoundData = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.status, RoundDataStatus::Answered);
+
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &PriceFeedQueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(100u64));
+        }
+
+        #[test]
+        fn expired_round_without_agreement_is_rejected() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(&mut app, price_collector_contract_addr, &[FEEDER1, FEEDER2]);
+
+            // all feeders answered, but only 200 is close enough to the median
+            // the round ids start from 1
+            let round_id = 1;
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(ADMIN, 100), (FEEDER1, 200), (FEEDER2, 400)],
+            );
+
+            // increase the block height to expire the round
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(100001),
+                height: app.block_info().height + DEFAULT_ROUND_DURATION + 100,
+                chain_id: app.block_info().chain_id,
+            });
+            provide_answers(&mut app, price_collector_contract_addr, &[(ADMIN, 100)]);
+
+            let res: RoundData = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::RoundData { round_id },
+                )
+                .unwrap();
+            assert_eq!(res.status, RoundDataStatus::Rejected);
+        }
+    }
+
+    mod config {
+        use cosmwasm_std::{BlockInfo, Decimal};
+
+        use crate::state::Config;
+
+        use super::*;
+
+        #[test]
+        fn only_owner_can_update_config() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+
+            // prepare the update config message
+            let update_config_msg = ExecuteMsg::UpdateConfig {
+                aggregation: None,
+                deviation_band: Some(Decimal::percent(2)),
+                quorum: Some(Decimal::percent(50)),
+                round_duration: Some(60),
+                reward: None,
+                staking: None,
+                commit_duration: None,
+                keeper_tip: None,
+            };
+
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &update_config_msg,
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &update_config_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res: Config = app
+                .wrap()
+                .query_wasm_smart(price_collector_contract_addr, &QueryMsg::Config {})
+                .unwrap();
+            assert_eq!(res.deviation_band, Decimal::percent(2));
+            assert_eq!(res.quorum, Decimal::percent(50));
+            assert_eq!(res.round_duration, 60);
+
+            // a quorum of 100% can never be reached
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::UpdateConfig {
+                    aggregation: None,
+                    deviation_band: None,
+                    quorum: Some(Decimal::one()),
+                    round_duration: None,
+                    reward: None,
+                    staking: None,
+                    commit_duration: None,
+                    keeper_tip: None,
+                },
+                &[],
+            );
+            assert!(res.is_err());
+
+            // a round must last at least 1 block
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::UpdateConfig {
+                    aggregation: None,
+                    deviation_band: None,
+                    quorum: None,
+                    round_duration: Some(0),
+                    reward: None,
+                    staking: None,
+                    commit_duration: None,
+                    keeper_tip: None,
+                },
+                &[],
+            );
+            assert!(res.is_err());
+        }
+
+        #[test]
+        fn round_follows_quorum_and_duration_of_config() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(&mut app, price_collector_contract_addr, &[FEEDER1, FEEDER2]);
+
+            // half of the feeders are enough and a round lasts 10 blocks
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::UpdateConfig {
+                    aggregation: None,
+                    deviation_band: None,
+                    quorum: Some(Decimal::percent(50)),
+                    round_duration: Some(10),
+                    reward: None,
+                    staking: None,
+                    commit_duration: None,
+                    keeper_tip: None,
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // 2 of 3 feeders is more than a half
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(ADMIN, 100), (FEEDER1, 100)],
+            );
+            let res: RoundData = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.status, RoundDataStatus::Answered);
+
+            // the round is closed after 10 blocks, so a new round is started
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(60),
+                height: app.block_info().height + 10,
+                chain_id: app.block_info().chain_id,
+            });
+            provide_answers(&mut app, price_collector_contract_addr, &[(FEEDER2, 100)]);
+            let res: RoundData = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.status, RoundDataStatus::Pending);
+            assert_eq!(res.started_at_height, app.block_info().height);
+        }
+    }
+
+    mod report {
+        use cosmwasm_std::{Binary, BlockInfo};
+        use k256::ecdsa::signature::hazmat::PrehashSigner;
+        use k256::ecdsa::{Signature, SigningKey};
+        use sha2::{Digest, Sha256};
+
+        use crate::msg::{FeederStatsResponse, Observation, Report, ReportSignature};
+        use crate::ContractError;
+
+        use super::*;
+
+        // a deterministic secp256k1 key of each feeder
+        fn signing_key(feeder: &str) -> SigningKey {
+            let secret = Sha256::digest(feeder.as_bytes());
+            SigningKey::from_bytes(&secret).unwrap()
+        }
+
+        // all feeders register their public keys to the price collector contract
+        fn register_pubkeys(app: &mut App, price_collector_contract_addr: &str, feeders: &[&str]) {
+            for feeder in feeders {
+                let pubkey = signing_key(feeder)
+                    .verifying_key()
+                    .to_encoded_point(true)
+                    .as_bytes()
+                    .to_vec();
+                let res = app.execute_contract(
+                    Addr::unchecked(*feeder),
+                    Addr::unchecked(price_collector_contract_addr),
+                    &ExecuteMsg::RegisterPubkey {
+                        pubkey: Binary::from(pubkey),
+                    },
+                    &[],
+                );
+                assert!(res.is_ok());
+            }
+        }
+
+        // the feeders sign the hash of the report
+        fn sign_report(
+            app: &App,
+            price_collector_contract_addr: &str,
+            report: &Report,
+            feeders: &[&str],
+        ) -> Vec<ReportSignature> {
+            let hash: Binary = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::ReportHash {
+                        report: report.clone(),
+                    },
+                )
+                .unwrap();
+            feeders
+                .iter()
+                .map(|feeder| {
+                    let signature: Signature = signing_key(feeder).sign_prehash(&hash).unwrap();
+                    ReportSignature {
+                        feeder: feeder.to_string(),
+                        signature: Binary::from(signature.to_bytes().to_vec()),
+                    }
+                })
+                .collect()
+        }
+
+        fn report(round_id: u64, observations: &[(&str, u64)]) -> Report {
+            Report {
+                round_id,
+                observations: observations
+                    .iter()
+                    .map(|(feeder, value)| Observation {
+                        feeder: feeder.to_string(),
+                        value: *value,
+                    })
+                    .collect(),
+            }
+        }
+
+        #[test]
+        fn relayer_can_submit_signed_report() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(&mut app, price_collector_contract_addr, &[FEEDER1, FEEDER2]);
+            register_pubkeys(
+                &mut app,
+                price_collector_contract_addr,
+                &[ADMIN, FEEDER1, FEEDER2],
+            );
+
+            // the report is for the first round
+            let report = report(1, &[(ADMIN, 100), (FEEDER1, 101), (FEEDER2, 99)]);
+            let signatures = sign_report(
+                &app,
+                price_collector_contract_addr,
+                &report,
+                &[ADMIN, FEEDER1, FEEDER2],
+            );
+
+            // anyone can relay the signed report
+            let submit_report_msg = ExecuteMsg::SubmitReport { report, signatures };
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &submit_report_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &PriceFeedQueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(100u64));
+
+            let res: RoundData = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.status, RoundDataStatus::Answered);
+            assert_eq!(res.answers.len(), 3);
+
+            // the same report cannot be submitted twice
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &submit_report_msg,
+                &[],
+            );
+            assert!(res.is_err());
+        }
+
+        #[test]
+        fn cannot_submit_forged_or_incomplete_report() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(&mut app, price_collector_contract_addr, &[FEEDER1, FEEDER2]);
+            register_pubkeys(
+                &mut app,
+                price_collector_contract_addr,
+                &[ADMIN, FEEDER1, FEEDER2],
+            );
+
+            // the relayer changes an answer after the feeders signed the report
+            // the round ids start from 1
+            let round_id = 1;
+            let signed_report = report(round_id, &[(ADMIN, 100), (FEEDER1, 101), (FEEDER2, 99)]);
+            let signatures = sign_report(
+                &app,
+                price_collector_contract_addr,
+                &signed_report,
+                &[ADMIN, FEEDER1, FEEDER2],
+            );
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::SubmitReport {
+                    report: report(round_id, &[(ADMIN, 100), (FEEDER1, 101), (FEEDER2, 999)]),
+                    signatures,
+                },
+                &[],
+            );
+            assert!(res.is_err());
+
+            // 2 of 3 feeders do not reach the quorum
+            let report = report(round_id, &[(ADMIN, 100), (FEEDER1, 101)]);
+            let signatures = sign_report(
+                &app,
+                price_collector_contract_addr,
+                &report,
+                &[ADMIN, FEEDER1],
+            );
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::SubmitReport { report, signatures },
+                &[],
+            );
+            assert!(res.is_err());
+        }
+
+        #[test]
+        fn report_closes_pending_round_first() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(
+                &mut app,
+                price_collector_contract_addr,
+                &[FEEDER1, FEEDER2, FEEDER3],
+            );
+            register_pubkeys(
+                &mut app,
+                price_collector_contract_addr,
+                &[ADMIN, FEEDER1, FEEDER2],
+            );
+
+            // the feeders do not agree on the answer, so the first round is pending
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(FEEDER1, 100), (FEEDER2, 102), (FEEDER3, 1000)],
+            );
+
+            // the report cannot replace the live round
+            let report = report(2, &[(ADMIN, 100), (FEEDER1, 101), (FEEDER2, 99)]);
+            let signatures = sign_report(
+                &app,
+                price_collector_contract_addr,
+                &report,
+                &[ADMIN, FEEDER1, FEEDER2],
+            );
+            let submit_report_msg = ExecuteMsg::SubmitReport { report, signatures };
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &submit_report_msg,
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::RoundInProgress {}.to_string()
+            );
+
+            // increase the block height to expire the round
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(100001),
+                height: app.block_info().height + DEFAULT_ROUND_DURATION + 100,
+                chain_id: app.block_info().chain_id,
+            });
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &submit_report_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // the expired round is rejected, and the missing feeder is counted
+            let res: RoundData = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::RoundData { round_id: 1 },
+                )
+                .unwrap();
+            assert_eq!(res.status, RoundDataStatus::Rejected);
+            let res: FeederStatsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::FeederStats {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.rounds_missed, 1);
+
+            let res: RoundData = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.status, RoundDataStatus::Answered);
+        }
+    }
+
+    mod feeders {
+        use crate::msg::FeederResponse;
+
+        use super::*;
+
+        #[test]
+        fn owner_can_manage_feeders() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+
+            // prepare the update price feeder message
+            let update_price_feeder_msg = ExecuteMsg::UpdatePriceFeeder {
+                price_feeder: FEEDER1.to_string(),
+                status: true,
+                moniker: Some("feeder one".to_string()),
+            };
+
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &update_price_feeder_msg,
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &update_price_feeder_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res: FeederResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::Feeder {
+                        address: FEEDER1.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.moniker, "feeder one");
+            assert!(res.active);
+            assert_eq!(res.joined_height, app.block_info().height);
+            assert_eq!(res.last_submission_height, None);
+
+            // the feeder provides an answer
+            provide_answers(&mut app, price_collector_contract_addr, &[(FEEDER1, 100)]);
+            let res: FeederResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::Feeder {
+                        address: FEEDER1.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.last_submission_height, Some(app.block_info().height));
+
+            // the removed feeder is kept as inactive and cannot provide answers anymore
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::UpdatePriceFeeder {
+                    price_feeder: FEEDER1.to_string(),
+                    status: false,
+                    moniker: None,
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res = app.execute_contract(
+                Addr::unchecked(FEEDER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::ProvideRoundData { answer: 100 },
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res: FeederResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::Feeder {
+                        address: FEEDER1.to_string(),
+                    },
+                )
+                .unwrap();
+            assert!(!res.active);
+            assert_eq!(res.moniker, "feeder one");
+        }
+
+        #[test]
+        fn feeders_are_listed_by_address() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(
+                &mut app,
+                price_collector_contract_addr,
+                &[FEEDER1, FEEDER2, FEEDER3],
+            );
+
+            let res: Vec<FeederResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::ListFeeders {
+                        start_after: None,
+                        limit: Some(2),
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                res.iter().map(|f| f.address.as_str()).collect::<Vec<_>>(),
+                vec![ADMIN, FEEDER1]
+            );
+
+            let res: Vec<FeederResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::ListFeeders {
+                        start_after: Some(FEEDER1.to_string()),
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                res.iter().map(|f| f.address.as_str()).collect::<Vec<_>>(),
+                vec![FEEDER2, FEEDER3]
+            );
+        }
+    }
+
+    mod stats {
+        use cosmwasm_std::{BlockInfo, Decimal};
+
+        use crate::msg::FeederStatsResponse;
+
+        use super::*;
+
+        fn query_stats(
+            app: &App,
+            price_collector_contract_addr: &str,
+            feeder: &str,
+        ) -> FeederStatsResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::FeederStats {
+                        address: feeder.to_string(),
+                    },
+                )
+                .unwrap()
+        }
+
+        #[test]
+        fn answered_round_updates_feeder_stats() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(
+                &mut app,
+                price_collector_contract_addr,
+                &[FEEDER1, FEEDER2, FEEDER3],
+            );
+
+            // 3 of 4 feeders answered, the median 100 is the answer of the round
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(ADMIN, 100), (FEEDER1, 110), (FEEDER2, 95)],
+            );
+
+            let res = query_stats(&app, price_collector_contract_addr, ADMIN);
+            assert_eq!(res.rounds_participated, 1);
+            assert_eq!(res.rounds_missed, 0);
+            assert_eq!(res.average_deviation, Decimal::zero());
+
+            let res = query_stats(&app, price_collector_contract_addr, FEEDER1);
+            assert_eq!(res.rounds_participated, 1);
+            assert_eq!(res.average_deviation, Decimal::percent(10));
+            assert_eq!(res.max_deviation, Decimal::percent(10));
+            assert_eq!(res.last_deviation, Some(Decimal::percent(10)));
+
+            // the last feeder did not answer before the round was finalized
+            let res = query_stats(&app, price_collector_contract_addr, FEEDER3);
+            assert_eq!(res.rounds_participated, 0);
+            assert_eq!(res.rounds_missed, 1);
+            assert_eq!(res.last_deviation, None);
+
+            // in the next round, the deviations are averaged
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(100001),
+                height: app.block_info().height + DEFAULT_ROUND_DURATION + 100,
+                chain_id: app.block_info().chain_id,
+            });
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(ADMIN, 100), (FEEDER1, 100), (FEEDER3, 100)],
+            );
+
+            let res = query_stats(&app, price_collector_contract_addr, FEEDER1);
+            assert_eq!(res.rounds_participated, 2);
+            assert_eq!(res.average_deviation, Decimal::percent(5));
+            assert_eq!(res.max_deviation, Decimal::percent(10));
+            assert_eq!(res.last_deviation, Some(Decimal::zero()));
+
+            let res = query_stats(&app, price_collector_contract_addr, FEEDER2);
+            assert_eq!(res.rounds_participated, 1);
+            assert_eq!(res.rounds_missed, 1);
+        }
+
+        #[test]
+        fn rejected_round_counts_participation_only() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(&mut app, price_collector_contract_addr, &[FEEDER1, FEEDER2]);
+
+            // all feeders answered, but they did not agree on the answer
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(ADMIN, 100), (FEEDER1, 200), (FEEDER2, 400)],
+            );
+
+            // increase the block height to expire the round
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(100001),
+                height: app.block_info().height + DEFAULT_ROUND_DURATION + 100,
+                chain_id: app.block_info().chain_id,
+            });
+            provide_answers(&mut app, price_collector_contract_addr, &[(ADMIN, 100)]);
+
+            let res = query_stats(&app, price_collector_contract_addr, FEEDER2);
+            assert_eq!(res.rounds_participated, 1);
+            assert_eq!(res.rounds_missed, 0);
+            assert_eq!(res.max_deviation, Decimal::zero());
+            assert_eq!(res.last_deviation, None);
+        }
+    }
+
+    mod rewards {
+        use cosmwasm_std::{coin, Coin};
+
+        use crate::tests::env_setup::env::NATIVE_DENOM;
+
+        use super::*;
+
+        // the owner sets the reward per answer and funds the reward pool
+        fn setup_rewards(app: &mut App, price_collector_contract_addr: &str, pool: u128) {
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::UpdateConfig {
+                    aggregation: None,
+                    deviation_band: None,
+                    quorum: None,
+                    round_duration: None,
+                    reward: Some(coin(100, NATIVE_DENOM)),
+                    staking: None,
+                    commit_duration: None,
+                    keeper_tip: None,
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::FundRewards {},
+                &[coin(pool, NATIVE_DENOM)],
+            );
+            assert!(res.is_ok());
+        }
+
+        fn query_pending_rewards(
+            app: &App,
+            price_collector_contract_addr: &str,
+            feeder: &str,
+        ) -> Uint128 {
+            app.wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::PendingRewards {
+                        address: feeder.to_string(),
+                    },
+                )
+                .unwrap()
+        }
+
+        #[test]
+        fn feeders_claim_rewards_of_counted_answers() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(
+                &mut app,
+                price_collector_contract_addr,
+                &[FEEDER1, FEEDER2, FEEDER3],
+            );
+            setup_rewards(&mut app, price_collector_contract_addr, 1000);
+
+            // 1000 is an outlier, so only 3 answers are counted
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(ADMIN, 100), (FEEDER1, 102), (FEEDER2, 1000), (FEEDER3, 98)],
+            );
+            assert_eq!(
+                query_pending_rewards(&app, price_collector_contract_addr, FEEDER1),
+                Uint128::from(100u128)
+            );
+            assert_eq!(
+                query_pending_rewards(&app, price_collector_contract_addr, FEEDER2),
+                Uint128::zero()
+            );
+            let res: Uint128 = app
+                .wrap()
+                .query_wasm_smart(price_collector_contract_addr, &QueryMsg::RewardPool {})
+                .unwrap();
+            assert_eq!(res, Uint128::from(700u128));
+
+            // the feeder claims its rewards
+            let res = app.execute_contract(
+                Addr::unchecked(FEEDER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::ClaimRewards {},
+                &[],
+            );
+            assert!(res.is_ok());
+            let balance: Coin = app.wrap().query_balance(FEEDER1, NATIVE_DENOM).unwrap();
+            assert_eq!(balance.amount, Uint128::from(100u128));
+
+            // nothing is left to claim
+            let res = app.execute_contract(
+                Addr::unchecked(FEEDER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::ClaimRewards {},
+                &[],
+            );
+            assert!(res.is_err());
+            let res = app.execute_contract(
+                Addr::unchecked(FEEDER2),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::ClaimRewards {},
+                &[],
+            );
+            assert!(res.is_err());
+        }
+
+        #[test]
+        fn rewards_stop_when_the_pool_runs_out() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(&mut app, price_collector_contract_addr, &[FEEDER1]);
+            setup_rewards(&mut app, price_collector_contract_addr, 150);
+
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(ADMIN, 100), (FEEDER1, 100)],
+            );
+            let total = query_pending_rewards(&app, price_collector_contract_addr, ADMIN)
+                + query_pending_rewards(&app, price_collector_contract_addr, FEEDER1);
+            assert_eq!(total, Uint128::from(100u128));
+        }
+
+        #[test]
+        fn only_owner_can_fund_rewards() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+
+            // the reward is not configured yet
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::FundRewards {},
+                &[coin(1000, NATIVE_DENOM)],
+            );
+            assert!(res.is_err());
+
+            setup_rewards(&mut app, price_collector_contract_addr, 1000);
+
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::FundRewards {},
+                &[],
+            );
+            assert!(res.is_err());
+
+            // the reward denom cannot be changed
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::UpdateConfig {
+                    aggregation: None,
+                    deviation_band: None,
+                    quorum: None,
+                    round_duration: None,
+                    reward: Some(coin(100, "uother")),
+                    staking: None,
+                    commit_duration: None,
+                    keeper_tip: None,
+                },
+                &[],
+            );
+            assert!(res.is_err());
+        }
+    }
+
+    mod staking {
+        use cosmwasm_std::{coin, coins, BlockInfo, Coin, Decimal};
+
+        use crate::msg::FeederResponse;
+        use crate::state::StakingConfig;
+        use crate::tests::env_setup::env::NATIVE_DENOM;
+
+        use super::*;
+
+        // the owner requires the feeders to bond 1000 coins, and sends coins to the feeders
+        fn setup_staking(app: &mut App, price_collector_contract_addr: &str, feeders: &[&str]) {
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::UpdateConfig {
+                    aggregation: None,
+                    deviation_band: None,
+                    quorum: None,
+                    round_duration: None,
+                    reward: None,
+                    staking: Some(StakingConfig {
+                        min_bond: coin(1000, NATIVE_DENOM),
+                        unbonding_period: 100,
+                        slash_fraction: Decimal::percent(10),
+                    }),
+                    commit_duration: None,
+                    keeper_tip: None,
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            for feeder in feeders {
+                app.send_tokens(
+                    Addr::unchecked(ADMIN),
+                    Addr::unchecked(*feeder),
+                    &coins(1000, NATIVE_DENOM),
+                )
+                .unwrap();
+            }
+        }
+
+        fn bond(app: &mut App, price_collector_contract_addr: &str, feeder: &str, amount: u128) {
+            let res = app.execute_contract(
+                Addr::unchecked(feeder),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::Bond { moniker: None },
+                &coins(amount, NATIVE_DENOM),
+            );
+            assert!(res.is_ok());
+        }
+
+        fn query_feeder(
+            app: &App,
+            price_collector_contract_addr: &str,
+            feeder: &str,
+        ) -> FeederResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::Feeder {
+                        address: feeder.to_string(),
+                    },
+                )
+                .unwrap()
+        }
+
+        #[test]
+        fn feeders_join_by_bonding_and_leave_by_unbonding() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            setup_staking(&mut app, price_collector_contract_addr, &[FEEDER1]);
+            add_feeders(&mut app, price_collector_contract_addr, &[FEEDER1]);
+
+            // the feeder added by the owner cannot provide answers without enough bond
+            bond(&mut app, price_collector_contract_addr, FEEDER1, 500);
+            let res = app.execute_contract(
+                Addr::unchecked(FEEDER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::ProvideRoundData { answer: 100 },
+                &[],
+            );
+            assert!(res.is_err());
+
+            // the feeder provides answers once it bonds enough coins
+            bond(&mut app, price_collector_contract_addr, FEEDER1, 500);
+            let res = query_feeder(&app, price_collector_contract_addr, FEEDER1);
+            assert!(res.active);
+            assert_eq!(res.bond, Uint128::from(1000u128));
+            provide_answers(&mut app, price_collector_contract_addr, &[(FEEDER1, 100)]);
+
+            // the feeder leaves, and its bond is locked for the unbonding period
+            let res = app.execute_contract(
+                Addr::unchecked(FEEDER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::Unbond {},
+                &[],
+            );
+            assert!(res.is_ok());
+            let res = app.execute_contract(
+                Addr::unchecked(FEEDER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::ProvideRoundData { answer: 100 },
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(FEEDER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::WithdrawUnbonded {},
+                &[],
+            );
+            assert!(res.is_err());
+
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(100),
+                height: app.block_info().height + 20,
+                chain_id: app.block_info().chain_id,
+            });
+            let res = app.execute_contract(
+                Addr::unchecked(FEEDER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::WithdrawUnbonded {},
+                &[],
+            );
+            assert!(res.is_ok());
+            let balance: Coin = app.wrap().query_balance(FEEDER1, NATIVE_DENOM).unwrap();
+            assert_eq!(balance.amount, Uint128::from(1000u128));
+        }
+
+        #[test]
+        fn outliers_of_rejected_round_are_slashed() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            setup_staking(
+                &mut app,
+                price_collector_contract_addr,
+                &[FEEDER1, FEEDER2, FEEDER3],
+            );
+            add_feeders(
+                &mut app,
+                price_collector_contract_addr,
+                &[FEEDER1, FEEDER2, FEEDER3],
+            );
+            for feeder in [FEEDER1, FEEDER2, FEEDER3] {
+                bond(&mut app, price_collector_contract_addr, feeder, 1000);
+            }
+
+            // 1000 is too far from the median, so not enough answers are accepted
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(FEEDER1, 100), (FEEDER2, 102), (FEEDER3, 1000)],
+            );
+
+            // increase the block height to expire the round
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(100001),
+                height: app.block_info().height + DEFAULT_ROUND_DURATION + 100,
+                chain_id: app.block_info().chain_id,
+            });
+            provide_answers(&mut app, price_collector_contract_addr, &[(FEEDER1, 100)]);
+
+            // only the outlier is slashed, so it does not bond enough to stay active
+            let res = query_feeder(&app, price_collector_contract_addr, FEEDER3);
+            assert_eq!(res.bond, Uint128::from(900u128));
+            let res = app.execute_contract(
+                Addr::unchecked(FEEDER3),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::ProvideRoundData { answer: 100 },
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = query_feeder(&app, price_collector_contract_addr, FEEDER2);
+            assert_eq!(res.bond, Uint128::from(1000u128));
+
+            // the slashed coins go to the reward pool
+            let res: Uint128 = app
+                .wrap()
+                .query_wasm_smart(price_collector_contract_addr, &QueryMsg::RewardPool {})
+                .unwrap();
+            assert_eq!(res, Uint128::from(100u128));
+        }
+
+        #[test]
+        fn removed_feeders_stay_inactive_after_bonding() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            setup_staking(&mut app, price_collector_contract_addr, &[FEEDER1, FEEDER2]);
+
+            // the owner removes the feeder
+            add_feeders(&mut app, price_collector_contract_addr, &[FEEDER1]);
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::UpdatePriceFeeder {
+                    price_feeder: FEEDER1.to_string(),
+                    status: false,
+                    moniker: None,
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // neither the removed feeder nor an unknown address can join by bonding
+            for feeder in [FEEDER1, FEEDER2] {
+                bond(&mut app, price_collector_contract_addr, feeder, 1000);
+                let res = query_feeder(&app, price_collector_contract_addr, feeder);
+                assert!(!res.active);
+                assert_eq!(res.bond, Uint128::from(1000u128));
+
+                let res = app.execute_contract(
+                    Addr::unchecked(feeder),
+                    Addr::unchecked(price_collector_contract_addr),
+                    &ExecuteMsg::ProvideRoundData { answer: 100 },
+                    &[],
+                );
+                assert!(res.is_err());
+            }
+        }
+    }
+
+    mod commit_reveal {
+        use cosmwasm_std::{Binary, BlockInfo};
+
+        use crate::contract::commitment_hash;
+        use crate::msg::FeederStatsResponse;
+        use crate::state::COMMITS;
+
+        use super::*;
+
+        // the owner turns the commit-reveal mode on with a commit phase of 10 blocks
+        fn enable_commit_reveal(app: &mut App, price_collector_contract_addr: &str) {
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::UpdateConfig {
+                    aggregation: None,
+                    deviation_band: None,
+                    quorum: None,
+                    round_duration: None,
+                    reward: None,
+                    staking: None,
+                    commit_duration: Some(10),
+                    keeper_tip: None,
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+        }
+
+        fn commit(
+            app: &mut App,
+            price_collector_contract_addr: &str,
+            feeder: &str,
+            answer: u64,
+        ) -> bool {
+            let commitment = commitment_hash(&Addr::unchecked(feeder), answer, "salt");
+            app.execute_contract(
+                Addr::unchecked(feeder),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::CommitRoundData {
+                    commitment: Binary::from(commitment),
+                },
+                &[],
+            )
+            .is_ok()
+        }
+
+        fn reveal(
+            app: &mut App,
+            price_collector_contract_addr: &str,
+            feeder: &str,
+            answer: u64,
+        ) -> bool {
+            app.execute_contract(
+                Addr::unchecked(feeder),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::RevealRoundData {
+                    answer,
+                    salt: "salt".to_string(),
+                },
+                &[],
+            )
+            .is_ok()
+        }
+
+        #[test]
+        fn feeders_commit_then_reveal_answers() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(
+                &mut app,
+                price_collector_contract_addr,
+                &[FEEDER1, FEEDER2, FEEDER3],
+            );
+            enable_commit_reveal(&mut app, price_collector_contract_addr);
+
+            // the answers cannot be provided directly
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::ProvideRoundData { answer: 100 },
+                &[],
+            );
+            assert!(res.is_err());
+
+            // the first commit starts the round, and the answers are hidden
+            for (feeder, answer) in [(ADMIN, 100), (FEEDER1, 102), (FEEDER2, 98), (FEEDER3, 99)] {
+                assert!(commit(
+                    &mut app,
+                    price_collector_contract_addr,
+                    feeder,
+                    answer
+                ));
+            }
+            let res: RoundData = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert!(res.answers.is_empty());
+
+            // the answers cannot be revealed in the commit phase
+            assert!(!reveal(&mut app, price_collector_contract_addr, ADMIN, 100));
+
+            // close the commit phase
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(50),
+                height: app.block_info().height + 10,
+                chain_id: app.block_info().chain_id,
+            });
+            assert!(!commit(&mut app, price_collector_contract_addr, ADMIN, 100));
+
+            // the last feeder reveals an answer which does not match its commitment
+            assert!(!reveal(
+                &mut app,
+                price_collector_contract_addr,
+                FEEDER3,
+                150
+            ));
+            for (feeder, answer) in [(ADMIN, 100), (FEEDER1, 102), (FEEDER2, 98)] {
+                assert!(reveal(
+                    &mut app,
+                    price_collector_contract_addr,
+                    feeder,
+                    answer
+                ));
+            }
+
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &PriceFeedQueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(100u64));
+
+            // the unrevealed commit is counted as a miss
+            let res: FeederStatsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::FeederStats {
+                        address: FEEDER3.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.rounds_missed, 1);
+        }
+
+        #[test]
+        fn unrevealed_commits_are_removed_when_round_is_closed() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(&mut app, price_collector_contract_addr, &[FEEDER1, FEEDER2]);
+            enable_commit_reveal(&mut app, price_collector_contract_addr);
+
+            // the feeders commit, but only one of them reveals its answer
+            for feeder in [ADMIN, FEEDER1, FEEDER2] {
+                assert!(commit(&mut app, price_collector_contract_addr, feeder, 100));
+            }
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(50),
+                height: app.block_info().height + 10,
+                chain_id: app.block_info().chain_id,
+            });
+            assert!(reveal(&mut app, price_collector_contract_addr, ADMIN, 100));
+
+            let commit_key = COMMITS.key((1, &Addr::unchecked(FEEDER1))).to_vec();
+            let res = app
+                .wrap()
+                .query_wasm_raw(price_collector_contract_addr, commit_key.clone())
+                .unwrap();
+            assert!(res.is_some());
+
+            // the next commit after the round expires closes the round
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(100001),
+                height: app.block_info().height + DEFAULT_ROUND_DURATION + 100,
+                chain_id: app.block_info().chain_id,
+            });
+            assert!(commit(
+                &mut app,
+                price_collector_contract_addr,
+                FEEDER1,
+                100
+            ));
+
+            let res = app
+                .wrap()
+                .query_wasm_raw(price_collector_contract_addr, commit_key)
+                .unwrap();
+            assert!(res.is_none());
+            let res = app
+                .wrap()
+                .query_wasm_raw(
+                    price_collector_contract_addr,
+                    COMMITS.key((2, &Addr::unchecked(FEEDER1))).to_vec(),
+                )
+                .unwrap();
+            assert!(res.is_some());
+        }
+
+        #[test]
+        fn unrevealed_commits_are_removed_when_round_is_forced() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(&mut app, price_collector_contract_addr, &[FEEDER1, FEEDER2]);
+            enable_commit_reveal(&mut app, price_collector_contract_addr);
+
+            assert!(commit(
+                &mut app,
+                price_collector_contract_addr,
+                FEEDER1,
+                100
+            ));
+            let commit_key = COMMITS.key((1, &Addr::unchecked(FEEDER1))).to_vec();
+            let res = app
+                .wrap()
+                .query_wasm_raw(price_collector_contract_addr, commit_key.clone())
+                .unwrap();
+            assert!(res.is_some());
+
+            // the owner forces the answer while the round is still live
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::ForceRoundData {
+                    answer: 100,
+                    reason: "the feeders are down".to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res = app
+                .wrap()
+                .query_wasm_raw(price_collector_contract_addr, commit_key)
+                .unwrap();
+            assert!(res.is_none());
+        }
+    }
+
+    mod finalize {
+        use cosmwasm_std::{coin, BlockInfo, Coin};
+
+        use crate::tests::env_setup::env::NATIVE_DENOM;
+
+        use super::*;
+
+        #[test]
+        fn anyone_can_finalize_round_reaching_quorum() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(
+                &mut app,
+                price_collector_contract_addr,
+                &[FEEDER1, FEEDER2, FEEDER3],
+            );
+
+            // the owner sets the keeper tip, which is paid from the reward pool
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::UpdateConfig {
+                    aggregation: None,
+                    deviation_band: None,
+                    quorum: None,
+                    round_duration: None,
+                    reward: Some(coin(0, NATIVE_DENOM)),
+                    staking: None,
+                    commit_duration: None,
+                    keeper_tip: Some(coin(10, NATIVE_DENOM)),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::FundRewards {},
+                &[coin(100, NATIVE_DENOM)],
+            );
+            assert!(res.is_ok());
+
+            // 2 of 4 feeders answered, so the round cannot be finalized
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(ADMIN, 100), (FEEDER1, 100)],
+            );
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::FinalizeRound {},
+                &[],
+            );
+            assert!(res.is_err());
+
+            // the silent feeders are removed, so the answers reach the quorum
+            for feeder in [FEEDER2, FEEDER3] {
+                let res = app.execute_contract(
+                    Addr::unchecked(ADMIN),
+                    Addr::unchecked(price_collector_contract_addr),
+                    &ExecuteMsg::UpdatePriceFeeder {
+                        price_feeder: feeder.to_string(),
+                        status: false,
+                        moniker: None,
+                    },
+                    &[],
+                );
+                assert!(res.is_ok());
+            }
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::FinalizeRound {},
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &PriceFeedQueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(100u64));
+
+            // the keeper is tipped
+            let balance: Coin = app.wrap().query_balance(USER1, NATIVE_DENOM).unwrap();
+            assert_eq!(balance.amount, Uint128::from(10u128));
+
+            // the round is already finalized
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::FinalizeRound {},
+                &[],
+            );
+            assert!(res.is_err());
+        }
+
+        #[test]
+        fn anyone_can_finalize_expired_round() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(&mut app, price_collector_contract_addr, &[FEEDER1, FEEDER2]);
+
+            // all feeders answered, but they did not agree on the answer
+            // the round ids start from 1
+            let round_id = 1;
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(ADMIN, 100), (FEEDER1, 200), (FEEDER2, 400)],
+            );
+
+            // increase the block height to expire the round
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(100001),
+                height: app.block_info().height + DEFAULT_ROUND_DURATION + 100,
+                chain_id: app.block_info().chain_id,
+            });
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::FinalizeRound {},
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res: RoundData = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::RoundData { round_id },
+                )
+                .unwrap();
+            assert_eq!(res.status, RoundDataStatus::Rejected);
+        }
+    }
+
+    mod manual {
+        use cosmwasm_std::BlockInfo;
+
+        use crate::msg::FeederStatsResponse;
+        use crate::state::ManualRound;
+        use crate::tests::env_setup::env::GUARDIAN;
+
+        use super::*;
+
+        #[test]
+        fn guardian_can_force_round_data() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+
+            // prepare the force round data message
+            let force_round_data_msg = ExecuteMsg::ForceRoundData {
+                answer: 100,
+                reason: "all feeders are down".to_string(),
+            };
+
+            let res = app.execute_contract(
+                Addr::unchecked(GUARDIAN),
+                Addr::unchecked(price_collector_contract_addr),
+                &force_round_data_msg,
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::UpdateGuardian {
+                    guardian: Some(GUARDIAN.to_string()),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // the round ids start from 1
+            let round_id = 1;
+            let res = app
+                .execute_contract(
+                    Addr::unchecked(GUARDIAN),
+                    Addr::unchecked(price_collector_contract_addr),
+                    &force_round_data_msg,
+                    &[],
+                )
+                .unwrap();
+            assert!(res
+                .events
+                .iter()
+                .any(|event| event.ty == "wasm-force_round_data"));
+
+            // the manual round is answered and pushed to the price feed
+            let res: RoundData = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.status, RoundDataStatus::Answered);
+
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &PriceFeedQueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(100u64));
+
+            let res: Option<ManualRound> = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::ManualRound { round_id },
+                )
+                .unwrap();
+            assert_eq!(res.unwrap().forced_by, Addr::unchecked(GUARDIAN));
+        }
+
+        #[test]
+        fn force_round_data_closes_pending_round_first() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(
+                &mut app,
+                price_collector_contract_addr,
+                &[FEEDER1, FEEDER2, FEEDER3],
+            );
+
+            // the feeders do not agree on the answer, so the first round is pending
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(FEEDER1, 100), (FEEDER2, 102), (FEEDER3, 1000)],
+            );
+
+            // the owner forces the answer in the next block
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(5),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::ForceRoundData {
+                    answer: 100,
+                    reason: "the feeders do not agree".to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // the pending round is rejected, and the missing feeder is counted
+            let res: RoundData = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::RoundData { round_id: 1 },
+                )
+                .unwrap();
+            assert_eq!(res.status, RoundDataStatus::Rejected);
+            let res: FeederStatsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::FeederStats {
+                        address: ADMIN.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.rounds_missed, 1);
+
+            let res: Option<ManualRound> = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::ManualRound { round_id: 2 },
+                )
+                .unwrap();
+            assert!(res.is_some());
+        }
+
+        #[test]
+        fn force_round_data_rejects_live_pending_round() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(&mut app, price_collector_contract_addr, &[FEEDER1, FEEDER2]);
+
+            // only one of the feeders answers, so the first round is pending
+            provide_answers(&mut app, price_collector_contract_addr, &[(FEEDER1, 100)]);
+
+            // the owner forces the answer while the round is still live
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(5),
+                height: app.block_info().height + 1,
+                chain_id: app.block_info().chain_id,
+            });
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::ForceRoundData {
+                    answer: 100,
+                    reason: "the feeders are down".to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // the superseded round is rejected with its answer
+            let res: RoundData = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::RoundData { round_id: 1 },
+                )
+                .unwrap();
+            assert_eq!(res.status, RoundDataStatus::Rejected);
+            assert_eq!(res.answers.len(), 1);
+
+            // the feeders are counted in the stats of the rejected round
+            for (feeder, rounds_participated, rounds_missed) in [(FEEDER1, 1, 0), (FEEDER2, 0, 1)] {
+                let res: FeederStatsResponse = app
+                    .wrap()
+                    .query_wasm_smart(
+                        price_collector_contract_addr,
+                        &QueryMsg::FeederStats {
+                            address: feeder.to_string(),
+                        },
+                    )
+                    .unwrap();
+                assert_eq!(res.rounds_participated, rounds_participated);
+                assert_eq!(res.rounds_missed, rounds_missed);
+            }
+
+            // the manual round follows the rejected round
+            let res: RoundData = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.status, RoundDataStatus::Answered);
+            let res: Option<ManualRound> = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::ManualRound { round_id: 2 },
+                )
+                .unwrap();
+            assert!(res.is_some());
+        }
+
+        #[test]
+        fn force_round_data_can_be_disabled_permanently() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::DisableForceRoundData {},
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::ForceRoundData {
+                    answer: 100,
+                    reason: "all feeders are down".to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_err());
+        }
+    }
+
+    mod fan_out {
+        use cosmwasm_std::BlockInfo;
+        use price_feed::msg::InstantiateMsg as PriceFeedInstantiateMsg;
+
+        use super::*;
+
+        // instantiate another price feed controlled by the price collector
+        fn instantiate_price_feed(
+            app: &mut App,
+            code_id: u64,
+            price_collector_contract_addr: &str,
+        ) -> String {
+            app.instantiate_contract(
+                code_id,
+                Addr::unchecked(ADMIN),
+                &PriceFeedInstantiateMsg {
+                    controller: price_collector_contract_addr.to_string(),
+                    decimals: 6,
+                    description: "AURA / VND mirror".to_string(),
+                    heartbeat: None,
+                    retention_policy: None,
+                    circuit_breaker: None,
+                },
+                &[],
+                "mirror price feed",
+                None,
+            )
+            .unwrap()
+            .to_string()
+        }
+
+        fn latest_answer(app: &App, price_feed_contract_addr: &str) -> Uint128 {
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &PriceFeedQueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            res.answer
+        }
+
+        #[test]
+        fn answers_are_pushed_to_every_price_feed() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            let mirror_contract_addr = instantiate_price_feed(
+                &mut app,
+                contracts[0].contract_code_id,
+                price_collector_contract_addr,
+            );
+
+            // only the owner can add a price feed, and only once
+            let add_price_feed_msg = ExecuteMsg::AddPriceFeed {
+                price_feed: mirror_contract_addr.clone(),
+            };
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &add_price_feed_msg,
+                &[],
+            );
+            assert!(res.is_err());
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &add_price_feed_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &add_price_feed_msg,
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res: Vec<Addr> = app
+                .wrap()
+                .query_wasm_smart(price_collector_contract_addr, &QueryMsg::PriceFeeds {})
+                .unwrap();
+            assert_eq!(res.len(), 2);
+
+            // the answer of the round is pushed to both price feeds
+            add_feeders(&mut app, price_collector_contract_addr, &[FEEDER1]);
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(ADMIN, 100), (FEEDER1, 100)],
+            );
+            assert_eq!(
+                latest_answer(&app, price_feed_contract_addr),
+                Uint128::from(100u64)
+            );
+            assert_eq!(
+                latest_answer(&app, &mirror_contract_addr),
+                Uint128::from(100u64)
+            );
+
+            // the removed price feed does not receive the next answers
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::RemovePriceFeed {
+                    price_feed: mirror_contract_addr.clone(),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(100001),
+                height: app.block_info().height + DEFAULT_ROUND_DURATION + 100,
+                chain_id: app.block_info().chain_id,
+            });
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(ADMIN, 200), (FEEDER1, 200)],
+            );
+            assert_eq!(
+                latest_answer(&app, price_feed_contract_addr),
+                Uint128::from(200u64)
+            );
+            assert_eq!(
+                latest_answer(&app, &mirror_contract_addr),
+                Uint128::from(100u64)
+            );
+        }
+    }
+
+    mod round_index {
+        use crate::msg::{OrderBy, RoundResponse};
+        use cosmwasm_std::{BlockInfo, Timestamp};
+
+        use super::*;
+
+        #[test]
+        fn rounds_are_found_by_height_and_time() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(&mut app, price_collector_contract_addr, &[FEEDER1, FEEDER2]);
+
+            // there is no round yet
+            let res: Option<RoundResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::RoundAtHeight { height: u64::MAX },
+                )
+                .unwrap();
+            assert_eq!(res, None);
+
+            let start = app.block_info();
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(ADMIN, 100), (FEEDER1, 101), (FEEDER2, 99)],
+            );
+
+            // the answer after the round expires starts the next round
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(100001),
+                height: app.block_info().height + DEFAULT_ROUND_DURATION + 100,
+                chain_id: app.block_info().chain_id,
+            });
+            provide_answers(&mut app, price_collector_contract_addr, &[(ADMIN, 100)]);
+
+            for (query_msg, round_id) in [
+                (
+                    QueryMsg::RoundAtHeight {
+                        height: start.height,
+                    },
+                    Some(1),
+                ),
+                (
+                    QueryMsg::RoundAtHeight {
+                        height: app.block_info().height - 1,
+                    },
+                    Some(1),
+                ),
+                (
+                    QueryMsg::RoundAtHeight {
+                        height: app.block_info().height,
+                    },
+                    Some(2),
+                ),
+                (
+                    QueryMsg::RoundAtHeight {
+                        height: start.height - 1,
+                    },
+                    None,
+                ),
+                (QueryMsg::RoundAtTime { time: start.time }, Some(1)),
+                (
+                    QueryMsg::RoundAtTime {
+                        time: app.block_info().time,
+                    },
+                    Some(2),
+                ),
+                (
+                    QueryMsg::RoundAtTime {
+                        time: Timestamp::from_seconds(0),
+                    },
+                    None,
+                ),
+            ] {
+                let res: Option<RoundResponse> = app
+                    .wrap()
+                    .query_wasm_smart(price_collector_contract_addr, &query_msg)
+                    .unwrap();
+                assert_eq!(res.map(|round| round.round_id), round_id);
+            }
+
+            // the round data is loaded by its exact round id
+            let res: RoundData = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::RoundData { round_id: 2 },
+                )
+                .unwrap();
+            assert_eq!(res.started_at_height, app.block_info().height);
+            assert_eq!(res.status, RoundDataStatus::Pending);
+            let res: StdResult<RoundData> = app.wrap().query_wasm_smart(
+                price_collector_contract_addr,
+                &QueryMsg::RoundData { round_id: 3 },
+            );
+            assert!(res.is_err());
+        }
+
+        #[test]
+        fn rounds_are_paginated() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+
+            // each answer after the previous round expires starts a new round
+            for answer in [100, 200, 300] {
+                provide_answers(&mut app, price_collector_contract_addr, &[(ADMIN, answer)]);
+                app.set_block(BlockInfo {
+                    time: app.block_info().time.plus_seconds(100001),
+                    height: app.block_info().height + DEFAULT_ROUND_DURATION + 100,
+                    chain_id: app.block_info().chain_id,
+                });
+            }
+
+            let res: Vec<RoundResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::Rounds {
+                        start_after: Some(1),
+                        limit: None,
+                        order: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                res.iter().map(|round| round.round_id).collect::<Vec<_>>(),
+                vec![2, 3]
+            );
+            assert_eq!(res[0].round_data.answers[0].value, 200);
+
+            let res: Vec<RoundResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::Rounds {
+                        start_after: None,
+                        limit: Some(1),
+                        order: Some(OrderBy::Descending),
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.len(), 1);
+            assert_eq!(res[0].round_id, 3);
+            assert_eq!(res[0].round_data.answers[0].value, 300);
+        }
+    }
+
+    mod ownership_transfer {
+        use crate::tests::env_setup::env::USER1;
+        use ownership::Ownership;
+
+        use super::*;
+
+        #[test]
+        fn owner_can_transfer_ownership() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+
+            // the owner proposes a new owner, who accepts the ownership
+            for (sender, msg) in [
+                (
+                    ADMIN,
+                    ExecuteMsg::ProposeNewOwner {
+                        new_owner: USER1.to_string(),
+                        expiry: None,
+                    },
+                ),
+                (USER1, ExecuteMsg::AcceptOwnership {}),
+            ] {
+                let res = app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(price_collector_contract_addr),
+                    &msg,
+                    &[],
+                );
+                assert!(res.is_ok());
+            }
+
+            let res: Ownership = app
+                .wrap()
+                .query_wasm_smart(price_collector_contract_addr, &QueryMsg::Ownership {})
+                .unwrap();
+            assert_eq!(res.owner, Some(Addr::unchecked(USER1)));
+        }
+    }
+
+    mod migration {
+        use crate::msg::{FeederResponse, MigrateMsg, RoundResponse};
+        use crate::state::{AggregationStrategy, Config};
+        use crate::tests::env_setup::env::{instantiate_legacy_price_collector, LegacyExecuteMsg};
+        use crate::ContractError;
+        use cosmwasm_std::{Decimal, Event};
+        use ownership::Ownership;
+
+        use super::*;
+
+        #[test]
+        fn migrate_from_v0_1() {
+            // get integration test app and a price collector of v0.1
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+            let (price_collector_contract_addr, price_collector_contract_code_id) =
+                instantiate_legacy_price_collector(&mut app, price_feed_contract_addr, "0.1.0");
+            let height = app.block_info().height;
+
+            let res = app.migrate_contract(
+                Addr::unchecked(ADMIN),
+                price_collector_contract_addr.clone(),
+                &MigrateMsg { limit: None },
+                price_collector_contract_code_id,
+            );
+            assert!(res.is_ok());
+
+            // the owner moves to the ownership and the new settings take the default values
+            let res: Ownership = app
+                .wrap()
+                .query_wasm_smart(&price_collector_contract_addr, &QueryMsg::Ownership {})
+                .unwrap();
+            assert_eq!(res.owner, Some(Addr::unchecked(ADMIN)));
+            let res: Config = app
+                .wrap()
+                .query_wasm_smart(&price_collector_contract_addr, &QueryMsg::Config {})
+                .unwrap();
+            assert_eq!(res.aggregation, AggregationStrategy::Median {});
+            assert_eq!(res.deviation_band, Decimal::percent(10));
+            assert_eq!(res.round_duration, DEFAULT_ROUND_DURATION);
+            let res: Vec<Addr> = app
+                .wrap()
+                .query_wasm_smart(&price_collector_contract_addr, &QueryMsg::PriceFeeds {})
+                .unwrap();
+            assert_eq!(res, vec![Addr::unchecked(price_feed_contract_addr)]);
+
+            // the feeders keep their status and join at the migration height
+            let res: Vec<FeederResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    &price_collector_contract_addr,
+                    &QueryMsg::ListFeeders {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                res.iter()
+                    .map(|f| (f.address.as_str(), f.active, f.joined_height))
+                    .collect::<Vec<_>>(),
+                vec![(FEEDER1, true, height), (FEEDER2, false, height)]
+            );
+
+            // the active feeder of v0.1 can still provide answers
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr.as_str(),
+                &[(FEEDER1, 100)],
+            );
+
+            // a newer version cannot be downgraded
+            let (newer_contract_addr, price_collector_contract_code_id) =
+                instantiate_legacy_price_collector(&mut app, price_feed_contract_addr, "9.0.0");
+            let res = app.migrate_contract(
+                Addr::unchecked(ADMIN),
+                newer_contract_addr,
+                &MigrateMsg { limit: None },
+                price_collector_contract_code_id,
+            );
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::CannotDowngrade {
+                    version: "9.0.0".to_string()
+                }
+                .to_string()
+            );
+        }
+
+        #[test]
+        fn migrate_from_v0_1_indexes_rounds_in_batches() {
+            // get integration test app and a price collector of v0.1 with 3 rounds
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+            let (price_collector_contract_addr, price_collector_contract_code_id) =
+                instantiate_legacy_price_collector(&mut app, price_feed_contract_addr, "0.1.0");
+            let first_height = app.block_info().height;
+            for answer in [100, 110, 120] {
+                app.update_block(|block| block.height += 2);
+                assert!(app
+                    .execute_contract(
+                        Addr::unchecked(FEEDER1),
+                        price_collector_contract_addr.clone(),
+                        &LegacyExecuteMsg::ProvideRoundData { answer },
+                        &[],
+                    )
+                    .is_ok());
+            }
+
+            // each migration indexes a bounded number of rounds until all of them are indexed
+            for (indexed_rounds, backfill_finished) in [("2", "false"), ("1", "true")] {
+                let res = app
+                    .migrate_contract(
+                        Addr::unchecked(ADMIN),
+                        price_collector_contract_addr.clone(),
+                        &MigrateMsg { limit: Some(2) },
+                        price_collector_contract_code_id,
+                    )
+                    .unwrap();
+                assert!(res.has_event(
+                    &Event::new("wasm")
+                        .add_attribute("indexed_rounds", indexed_rounds)
+                        .add_attribute("backfill_finished", backfill_finished)
+                ));
+            }
+
+            // the old rounds are found by their block height
+            for (height, round_id, answer) in [
+                (first_height + 1, None, None),
+                (first_height + 2, Some(first_height + 2), Some(100)),
+                (first_height + 5, Some(first_height + 4), Some(110)),
+                (first_height + 6, Some(first_height + 6), Some(120)),
+            ] {
+                let res: Option<RoundResponse> = app
+                    .wrap()
+                    .query_wasm_smart(
+                        &price_collector_contract_addr,
+                        &QueryMsg::RoundAtHeight { height },
+                    )
+                    .unwrap();
+                assert_eq!(res.as_ref().map(|r| r.round_id), round_id);
+                assert_eq!(res.map(|r| r.round_data.answers[0].value), answer);
+            }
+
+            // the new rounds continue from the latest round of v0.1 and are indexed when saved
+            app.update_block(|block| block.height += DEFAULT_ROUND_DURATION);
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr.as_str(),
+                &[(FEEDER1, 130)],
+            );
+            let res: Option<RoundResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    &price_collector_contract_addr,
+                    &QueryMsg::RoundAtHeight {
+                        height: app.block_info().height,
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.unwrap().round_id, first_height + 7);
+        }
+    }
+
+    mod pause {
+        use crate::state::Operation;
+        use crate::tests::env_setup::env::GUARDIAN;
+        use crate::ContractError;
+
+        use super::*;
+
+        #[test]
+        fn guardian_can_pause_provide_round_data() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_collector_contract_addr = &contracts[1].contract_addr;
+            add_feeders(&mut app, price_collector_contract_addr, &[FEEDER1, FEEDER2]);
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::UpdateGuardian {
+                    guardian: Some(GUARDIAN.to_string()),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // only the owner or the guardian can pause the answers
+            for (sender, authorized) in [(FEEDER1, false), (GUARDIAN, true)] {
+                let res = app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(price_collector_contract_addr),
+                    &ExecuteMsg::Pause {
+                        operation: Operation::ProvideRoundData,
+                    },
+                    &[],
+                );
+                assert_eq!(res.is_ok(), authorized);
+            }
+
+            // the answers are rejected, but the queries keep working
+            let res = app.execute_contract(
+                Addr::unchecked(FEEDER1),
+                Addr::unchecked(price_collector_contract_addr),
+                &ExecuteMsg::ProvideRoundData { answer: 100 },
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::Paused {}.to_string()
+            );
+            let res: Vec<String> = app
+                .wrap()
+                .query_wasm_smart(
+                    price_collector_contract_addr,
+                    &QueryMsg::PausedOperations {},
+                )
+                .unwrap();
+            assert_eq!(res, vec!["provide_round_data".to_string()]);
+
+            // only the owner can resume the answers
+            for (sender, authorized) in [(GUARDIAN, false), (ADMIN, true)] {
+                let res = app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(price_collector_contract_addr),
+                    &ExecuteMsg::Unpause {
+                        operation: Operation::ProvideRoundData,
+                    },
+                    &[],
+                );
+                assert_eq!(res.is_ok(), authorized);
+            }
+            provide_answers(
+                &mut app,
+                price_collector_contract_addr,
+                &[(FEEDER1, 100), (FEEDER2, 100)],
+            );
+        }
+    }
 }
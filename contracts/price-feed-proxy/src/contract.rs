@@ -0,0 +1,230 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, to_vec, Binary, ContractResult, Deps, DepsMut, Empty, Env, MessageInfo,
+    QueryRequest, Response, StdError, StdResult, SystemResult, WasmQuery,
+};
+use cw2::{get_contract_version, set_contract_version};
+use ownership::{
+    accept_ownership, get_ownership, initialize_owner, is_owner, propose_new_owner,
+    renounce_ownership,
+};
+use semver::Version;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, PriceFeedQueryMsg, ProxyQueryMsg, QueryMsg,
+};
+use crate::state::{Config, ProposedAggregator, CONFIG, PROPOSED_AGGREGATOR};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:price-feed-proxy";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_DELAY: u64 = 86400; // the default delay before confirming a new aggregator, 1 day
+
+/// Handling contract instantiation
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // the sender is the first owner
+    initialize_owner(deps.storage, deps.api, info.sender.as_str())?;
+
+    // init config
+    let config = Config {
+        aggregator: deps.api.addr_validate(&msg.aggregator)?,
+        delay: msg.delay.unwrap_or(DEFAULT_DELAY),
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "instantiate"),
+        ("owner", info.sender.as_str()),
+        ("aggregator", &msg.aggregator),
+    ]))
+}
+
+/// Handling contract execution
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ProposeAggregator { aggregator } => {
+            propose_aggregator(deps, env, info, aggregator)
+        }
+        ExecuteMsg::ConfirmAggregator {} => confirm_aggregator(deps, env, info),
+        ExecuteMsg::CancelProposedAggregator {} => cancel_proposed_aggregator(deps, info),
+        ExecuteMsg::ProposeNewOwner { new_owner, expiry } => {
+            Ok(propose_new_owner(deps, &env, &info, new_owner, expiry)?)
+        }
+        ExecuteMsg::AcceptOwnership {} => Ok(accept_ownership(deps, &env, &info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(renounce_ownership(deps, &env, &info)?),
+    }
+}
+
+/// Handling contract migration
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // only an older or the same version of the price feed proxy can be migrated
+    let previous = get_contract_version(deps.storage)?;
+    if previous.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigration {
+            contract: previous.contract,
+            version: previous.version,
+        });
+    }
+    let previous_version: Version = previous.version.parse()?;
+    if previous_version > CONTRACT_VERSION.parse::<Version>()? {
+        return Err(ContractError::CannotDowngrade {
+            version: previous.version,
+        });
+    }
+
+    // the state layout has not changed since the first version
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "migrate"),
+        ("previous_version", previous.version.as_str()),
+        ("version", CONTRACT_VERSION),
+    ]))
+}
+
+/// Handling contract query
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Proxy(ProxyQueryMsg::Ownership {}) => to_binary(&get_ownership(deps.storage)?),
+        QueryMsg::Proxy(ProxyQueryMsg::Config {}) => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Proxy(ProxyQueryMsg::ProposedAggregator {}) => {
+            to_binary(&PROPOSED_AGGREGATOR.may_load(deps.storage)?)
+        }
+        QueryMsg::PriceFeed(msg) => {
+            let aggregator = CONFIG.load(deps.storage)?.aggregator;
+            forward_query(deps, aggregator.as_str(), &msg)
+        }
+    }
+}
+
+// forward the query to the aggregator and return its raw response
+fn forward_query(deps: Deps, aggregator: &str, msg: &PriceFeedQueryMsg) -> StdResult<Binary> {
+    let request: QueryRequest<Empty> = QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: aggregator.to_string(),
+        msg: to_binary(msg)?,
+    });
+
+    match deps.querier.raw_query(&to_vec(&request)?) {
+        SystemResult::Err(system_err) => Err(StdError::generic_err(format!(
+            "Querier system error: {}",
+            system_err
+        ))),
+        SystemResult::Ok(ContractResult::Err(contract_err)) => Err(StdError::generic_err(format!(
+            "Querier contract error: {}",
+            contract_err
+        ))),
+        SystemResult::Ok(ContractResult::Ok(value)) => Ok(value),
+    }
+}
+
+pub fn propose_aggregator(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    aggregator: String,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+
+    // the new aggregator must answer with the same decimals, otherwise the consumers would misread the answers
+    let aggregator = deps.api.addr_validate(&aggregator)?;
+    let decimals: u8 = deps
+        .querier
+        .query_wasm_smart(&config.aggregator, &PriceFeedQueryMsg::Decimals {})?;
+    let new_decimals: u8 = deps
+        .querier
+        .query_wasm_smart(&aggregator, &PriceFeedQueryMsg::Decimals {})?;
+    if decimals != new_decimals {
+        return Err(ContractError::DecimalsMismatch {});
+    }
+
+    // a new proposal replaces the previous one and restarts the delay
+    let proposed_aggregator = ProposedAggregator {
+        aggregator: aggregator.clone(),
+        confirmable_at: env.block.time.plus_seconds(config.delay),
+    };
+    PROPOSED_AGGREGATOR.save(deps.storage, &proposed_aggregator)?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "propose_aggregator"),
+        ("aggregator", aggregator.as_str()),
+        (
+            "confirmable_at",
+            &proposed_aggregator.confirmable_at.seconds().to_string(),
+        ),
+    ]))
+}
+
+pub fn confirm_aggregator(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+
+    let proposed_aggregator = PROPOSED_AGGREGATOR
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoProposedAggregator {})?;
+    if env.block.time < proposed_aggregator.confirmable_at {
+        return Err(ContractError::DelayNotPassed {
+            confirmable_at: proposed_aggregator.confirmable_at.seconds(),
+        });
+    }
+
+    // switch the queries to the new aggregator
+    let previous_aggregator = config.aggregator;
+    config.aggregator = proposed_aggregator.aggregator;
+    CONFIG.save(deps.storage, &config)?;
+    PROPOSED_AGGREGATOR.remove(deps.storage);
+
+    Ok(Response::new().add_attributes([
+        ("method", "confirm_aggregator"),
+        ("previous_aggregator", previous_aggregator.as_str()),
+        ("aggregator", config.aggregator.as_str()),
+    ]))
+}
+
+pub fn cancel_proposed_aggregator(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !PROPOSED_AGGREGATOR.exists(deps.storage) {
+        return Err(ContractError::NoProposedAggregator {});
+    }
+    PROPOSED_AGGREGATOR.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("method", "cancel_proposed_aggregator"))
+}
@@ -0,0 +1,37 @@
+use cosmwasm_std::StdError;
+use ownership::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("{0}")]
+    SemVer(#[from] semver::Error),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Custom Error val: {val:?}")]
+    CustomError { val: String },
+    // Add any other custom errors you like here.
+    // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
+    #[error("Decimals of the aggregator do not match")]
+    DecimalsMismatch {},
+
+    #[error("No proposed aggregator")]
+    NoProposedAggregator {},
+
+    #[error("Proposed aggregator cannot be confirmed before {confirmable_at}")]
+    DelayNotPassed { confirmable_at: u64 },
+
+    #[error("Cannot migrate from {contract} {version}")]
+    InvalidMigration { contract: String, version: String },
+
+    #[error("Cannot migrate from the newer version {version}")]
+    CannotDowngrade { version: String },
+}
@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw_utils::Expiration;
+use ownership::Ownership;
+use schemars::schema::RootSchema;
+
+pub use price_feed::msg::QueryMsg as PriceFeedQueryMsg;
+
+use crate::state::{Config, ProposedAggregator};
+
+/// Message type for `instantiate` entry_point
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub aggregator: String,
+    // the number of seconds a proposed aggregator must wait before it is confirmed, default is 1 day
+    pub delay: Option<u64>,
+}
+
+/// Message type for `execute` entry_point
+#[cw_serde]
+pub enum ExecuteMsg {
+    ProposeAggregator {
+        aggregator: String,
+    },
+    ConfirmAggregator {},
+    CancelProposedAggregator {},
+    // propose a new owner, who must accept the ownership before the expiry
+    ProposeNewOwner {
+        new_owner: String,
+        expiry: Option<Expiration>,
+    },
+    AcceptOwnership {},
+    // give up the ownership permanently
+    RenounceOwnership {},
+}
+
+/// Message type for `migrate` entry_point
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// Message type for `query` entry_point, the price feed queries are forwarded to the aggregator
+#[cw_serde]
+#[serde(untagged)]
+pub enum QueryMsg {
+    Proxy(ProxyQueryMsg),
+    PriceFeed(PriceFeedQueryMsg),
+}
+
+// the proxy queries shadow the price feed queries with the same name (like Ownership),
+// so the nested responses are merged instead of being rejected as a name collision
+impl QueryResponses for QueryMsg {
+    fn response_schemas_impl() -> BTreeMap<String, RootSchema> {
+        let mut response_schemas = PriceFeedQueryMsg::response_schemas_impl();
+        response_schemas.extend(ProxyQueryMsg::response_schemas_impl());
+        response_schemas
+    }
+}
+
+/// The queries answered by the proxy itself
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum ProxyQueryMsg {
+    #[returns(Config)]
+    Config {},
+    #[returns(Option<ProposedAggregator>)]
+    ProposedAggregator {},
+    // the ownership of the proxy itself, not of the aggregator
+    #[returns(Ownership)]
+    Ownership {},
+}
@@ -0,0 +1,24 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Timestamp};
+use cw_storage_plus::Item;
+
+// the config of the price feed proxy
+#[cw_serde]
+pub struct Config {
+    // the price feed which the queries are forwarded to
+    pub aggregator: Addr,
+    // the number of seconds between proposing and confirming a new aggregator
+    pub delay: u64,
+}
+
+// the aggregator proposed by the owner, it replaces the current one after the delay
+#[cw_serde]
+pub struct ProposedAggregator {
+    pub aggregator: Addr,
+    pub confirmable_at: Timestamp,
+}
+
+// the config data
+pub const CONFIG: Item<Config> = Item::new("config");
+// the proposed aggregator, if any
+pub const PROPOSED_AGGREGATOR: Item<ProposedAggregator> = Item::new("proposed_aggregator");
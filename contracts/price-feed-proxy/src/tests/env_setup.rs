@@ -0,0 +1,115 @@
+#[cfg(test)]
+pub mod env {
+    use cosmwasm_std::{Addr, Empty};
+
+    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+
+    use crate::contract::{execute, instantiate, migrate, query};
+    use crate::msg::InstantiateMsg;
+
+    use price_feed::contract::{
+        execute as PriceFeedExecute, instantiate as PriceFeedInstantiate, query as PriceFeedQuery,
+    };
+    use price_feed::msg::InstantiateMsg as PriceFeedInstantiateMsg;
+
+    pub const ADMIN: &str = "aura1000000000000000000000000000000000admin";
+    pub const USER1: &str = "aura1000000000000000000000000000000000user1";
+    pub const CONTROLLER: &str = "aura10000000000000000000000000000controller";
+
+    // the delay before confirming a new aggregator
+    pub const DELAY: u64 = 3600;
+
+    pub struct ContractInfo {
+        pub contract_addr: String,
+        pub contract_code_id: u64,
+    }
+
+    // create app instance
+    fn mock_app() -> App {
+        AppBuilder::new().build(|_, _, _| {})
+    }
+
+    // create price feed proxy contract
+    pub fn price_feed_proxy_contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(execute, instantiate, query).with_migrate(migrate);
+        Box::new(contract)
+    }
+
+    // create price feed contract
+    pub fn price_feed_contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(PriceFeedExecute, PriceFeedInstantiate, PriceFeedQuery);
+        Box::new(contract)
+    }
+
+    pub fn instantiate_contracts() -> (App, Vec<ContractInfo>) {
+        // Create a new app instance
+        let mut app = mock_app();
+        // Create a vector to store all contract info ([price feed] - [0], [new price feed] - [1],
+        // [price feed with other decimals] - [2], [price feed proxy] - [3])
+        let mut contract_info_vec: Vec<ContractInfo> = Vec::new();
+
+        // store code of all contracts to the app and get the code ids
+        let price_feed_contract_code_id = app.store_code(price_feed_contract_template());
+        let price_feed_proxy_contract_code_id =
+            app.store_code(price_feed_proxy_contract_template());
+
+        // instantiate price feed contracts
+        for decimals in [6, 6, 8] {
+            let price_feed_contract_addr = app
+                .instantiate_contract(
+                    price_feed_contract_code_id,
+                    Addr::unchecked(ADMIN),
+                    &PriceFeedInstantiateMsg {
+                        controller: CONTROLLER.to_string(),
+                        decimals,
+                        description: "AURA / USD".to_string(),
+                        heartbeat: None,
+                        retention_policy: None,
+                        circuit_breaker: None,
+                    },
+                    &[],
+                    "test instantiate contract",
+                    None,
+                )
+                .unwrap();
+
+            // add contract info to the vector
+            contract_info_vec.push(ContractInfo {
+                contract_addr: price_feed_contract_addr.to_string(),
+                contract_code_id: price_feed_contract_code_id,
+            });
+        }
+
+        // instantiate price feed proxy contract pointing to the first price feed
+        let price_feed_proxy_contract_addr = app
+            .instantiate_contract(
+                price_feed_proxy_contract_code_id,
+                Addr::unchecked(ADMIN),
+                &InstantiateMsg {
+                    aggregator: contract_info_vec[0].contract_addr.clone(),
+                    delay: Some(DELAY),
+                },
+                &[],
+                "test instantiate contract",
+                Some(ADMIN.to_string()),
+            )
+            .unwrap();
+
+        // add contract info to the vector
+        contract_info_vec.push(ContractInfo {
+            contract_addr: price_feed_proxy_contract_addr.to_string(),
+            contract_code_id: price_feed_proxy_contract_code_id,
+        });
+
+        // return the app instance and contract info vector
+        (app, contract_info_vec)
+    }
+
+    #[test]
+    fn test_instantiate_contracts() {
+        let (_app, contract_info_vec) = instantiate_contracts();
+
+        // check if all contracts are instantiated
+        assert_eq!(contract_info_vec.len(), 4);
+    }
+}
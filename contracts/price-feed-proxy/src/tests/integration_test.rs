@@ -0,0 +1,369 @@
+#![cfg(test)]
+mod tests {
+    use crate::msg::{ExecuteMsg, PriceFeedQueryMsg, ProxyQueryMsg, QueryMsg};
+    use crate::state::{Config, ProposedAggregator};
+    use crate::tests::env_setup::env::{instantiate_contracts, ADMIN, CONTROLLER, DELAY, USER1};
+    use cosmwasm_std::{Addr, BlockInfo, StdResult, Uint128};
+    use cw_multi_test::{App, Executor};
+    use price_feed::msg::{ExecuteMsg as PriceFeedExecuteMsg, RoundDataResponse};
+
+    // the controller updates the answer of a price feed
+    fn update_answer(app: &mut App, price_feed_contract_addr: &str, answer: u64) {
+        let res = app.execute_contract(
+            Addr::unchecked(CONTROLLER),
+            Addr::unchecked(price_feed_contract_addr),
+            &PriceFeedExecuteMsg::UpdateRoundData { answer },
+            &[],
+        );
+        assert!(res.is_ok());
+    }
+
+    fn wait(app: &mut App, seconds: u64) {
+        app.set_block(BlockInfo {
+            time: app.block_info().time.plus_seconds(seconds),
+            height: app.block_info().height + seconds / 5,
+            chain_id: app.block_info().chain_id,
+        });
+    }
+
+    mod price_feed_proxy_testing {
+        use super::*;
+
+        #[test]
+        fn proxy_forwards_queries_to_aggregator() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+            let price_feed_proxy_contract_addr = &contracts[3].contract_addr;
+
+            update_answer(&mut app, price_feed_contract_addr, 100);
+
+            // the consumers query the proxy with the price feed messages
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_proxy_contract_addr,
+                    &PriceFeedQueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(100u64));
+
+            let res: u8 = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_proxy_contract_addr,
+                    &PriceFeedQueryMsg::Decimals {},
+                )
+                .unwrap();
+            assert_eq!(res, 6);
+
+            // the errors of the aggregator are forwarded too
+            let res: StdResult<RoundDataResponse> = app.wrap().query_wasm_smart(
+                price_feed_proxy_contract_addr,
+                &PriceFeedQueryMsg::LatestFreshRoundData { max_age: Some(0) },
+            );
+            assert!(res.is_ok());
+            wait(&mut app, 10);
+            let res: StdResult<RoundDataResponse> = app.wrap().query_wasm_smart(
+                price_feed_proxy_contract_addr,
+                &PriceFeedQueryMsg::LatestFreshRoundData { max_age: Some(0) },
+            );
+            assert!(res.is_err());
+
+            let res: Config = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_proxy_contract_addr,
+                    &QueryMsg::Proxy(ProxyQueryMsg::Config {}),
+                )
+                .unwrap();
+            assert_eq!(res.aggregator, Addr::unchecked(price_feed_contract_addr));
+            assert_eq!(res.delay, DELAY);
+        }
+
+        #[test]
+        fn only_owner_can_propose_aggregator() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let new_price_feed_contract_addr = &contracts[1].contract_addr;
+            let price_feed_proxy_contract_addr = &contracts[3].contract_addr;
+
+            let propose_aggregator_msg = ExecuteMsg::ProposeAggregator {
+                aggregator: new_price_feed_contract_addr.to_string(),
+            };
+
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_feed_proxy_contract_addr),
+                &propose_aggregator_msg,
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_proxy_contract_addr),
+                &propose_aggregator_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res: Option<ProposedAggregator> = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_proxy_contract_addr,
+                    &QueryMsg::Proxy(ProxyQueryMsg::ProposedAggregator {}),
+                )
+                .unwrap();
+            let proposed_aggregator = res.unwrap();
+            assert_eq!(
+                proposed_aggregator.aggregator,
+                Addr::unchecked(new_price_feed_contract_addr)
+            );
+            assert_eq!(
+                proposed_aggregator.confirmable_at,
+                app.block_info().time.plus_seconds(DELAY)
+            );
+        }
+
+        #[test]
+        fn cannot_propose_aggregator_with_other_decimals() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let other_price_feed_contract_addr = &contracts[2].contract_addr;
+            let price_feed_proxy_contract_addr = &contracts[3].contract_addr;
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_proxy_contract_addr),
+                &ExecuteMsg::ProposeAggregator {
+                    aggregator: other_price_feed_contract_addr.to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_err());
+
+            // the address which is not a price feed is rejected too
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_proxy_contract_addr),
+                &ExecuteMsg::ProposeAggregator {
+                    aggregator: USER1.to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_err());
+        }
+
+        #[test]
+        fn proposed_aggregator_is_confirmed_after_delay() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+            let new_price_feed_contract_addr = &contracts[1].contract_addr;
+            let price_feed_proxy_contract_addr = &contracts[3].contract_addr;
+
+            update_answer(&mut app, price_feed_contract_addr, 100);
+            update_answer(&mut app, new_price_feed_contract_addr, 200);
+
+            // cannot confirm without a proposal
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_proxy_contract_addr),
+                &ExecuteMsg::ConfirmAggregator {},
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_proxy_contract_addr),
+                &ExecuteMsg::ProposeAggregator {
+                    aggregator: new_price_feed_contract_addr.to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // the delay has not passed yet
+            wait(&mut app, DELAY - 5);
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_proxy_contract_addr),
+                &ExecuteMsg::ConfirmAggregator {},
+                &[],
+            );
+            assert!(res.is_err());
+
+            // the answers still come from the current aggregator
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_proxy_contract_addr,
+                    &PriceFeedQueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(100u64));
+
+            wait(&mut app, 5);
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_feed_proxy_contract_addr),
+                &ExecuteMsg::ConfirmAggregator {},
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_proxy_contract_addr),
+                &ExecuteMsg::ConfirmAggregator {},
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // the answers come from the new aggregator
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_proxy_contract_addr,
+                    &PriceFeedQueryMsg::LastestRoundData {},
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(200u64));
+
+            let res: Option<ProposedAggregator> = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_proxy_contract_addr,
+                    &QueryMsg::Proxy(ProxyQueryMsg::ProposedAggregator {}),
+                )
+                .unwrap();
+            assert!(res.is_none());
+        }
+
+        #[test]
+        fn owner_can_cancel_proposed_aggregator() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let new_price_feed_contract_addr = &contracts[1].contract_addr;
+            let price_feed_proxy_contract_addr = &contracts[3].contract_addr;
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_proxy_contract_addr),
+                &ExecuteMsg::ProposeAggregator {
+                    aggregator: new_price_feed_contract_addr.to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(price_feed_proxy_contract_addr),
+                &ExecuteMsg::CancelProposedAggregator {},
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_proxy_contract_addr),
+                &ExecuteMsg::CancelProposedAggregator {},
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // the cancelled aggregator cannot be confirmed
+            wait(&mut app, DELAY);
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_proxy_contract_addr),
+                &ExecuteMsg::ConfirmAggregator {},
+                &[],
+            );
+            assert!(res.is_err());
+        }
+    }
+
+    mod ownership_transfer {
+        use crate::tests::env_setup::env::USER1;
+        use ownership::Ownership;
+
+        use super::*;
+
+        #[test]
+        fn owner_can_transfer_ownership() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_proxy_contract_addr = &contracts[3].contract_addr;
+
+            // the owner proposes a new owner, who accepts the ownership
+            for (sender, msg) in [
+                (
+                    ADMIN,
+                    ExecuteMsg::ProposeNewOwner {
+                        new_owner: USER1.to_string(),
+                        expiry: None,
+                    },
+                ),
+                (USER1, ExecuteMsg::AcceptOwnership {}),
+            ] {
+                let res = app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(price_feed_proxy_contract_addr),
+                    &msg,
+                    &[],
+                );
+                assert!(res.is_ok());
+            }
+
+            let res: Ownership = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_proxy_contract_addr,
+                    &QueryMsg::Proxy(ProxyQueryMsg::Ownership {}),
+                )
+                .unwrap();
+            assert_eq!(res.owner, Some(Addr::unchecked(USER1)));
+        }
+    }
+
+    mod migration {
+        use crate::msg::MigrateMsg;
+
+        use super::*;
+
+        #[test]
+        fn admin_can_migrate_and_keep_aggregator() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+            let price_feed_proxy_contract_addr = &contracts[3].contract_addr;
+            let price_feed_proxy_contract_code_id = contracts[3].contract_code_id;
+
+            // only the admin of the contract can migrate it
+            for (sender, authorized) in [(USER1, false), (ADMIN, true)] {
+                let res = app.migrate_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(price_feed_proxy_contract_addr),
+                    &MigrateMsg {},
+                    price_feed_proxy_contract_code_id,
+                );
+                assert_eq!(res.is_ok(), authorized);
+            }
+
+            // the aggregator is kept
+            let res: Config = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_proxy_contract_addr,
+                    &QueryMsg::Proxy(ProxyQueryMsg::Config {}),
+                )
+                .unwrap();
+            assert_eq!(res.aggregator, Addr::unchecked(price_feed_contract_addr));
+            assert_eq!(res.delay, DELAY);
+        }
+    }
+}
@@ -1,47 +1,99 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
+    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, Event, MessageInfo, Order,
+    Reply, ReplyOn, Response, StdError, StdResult, Storage, SubMsg, SubMsgResult, Timestamp,
+    Uint128, WasmMsg,
 };
-use cw2::set_contract_version;
-use cw_storage_plus::Bound;
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::{Bound, Map};
+use ownership::{
+    accept_ownership, get_ownership, initialize_owner, is_owner, propose_new_owner,
+    renounce_ownership,
+};
+use semver::Version;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, RoundDataResponse};
-use crate::state::{RoundData, CONFIG, PRICE_FEED_INFO, ROUND_DATA};
+use crate::migrations::{backfill_round_indexes, migrate_from_v0_1};
+use crate::msg::{
+    AggregatorRoundDataResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, OrderBy, PriceFeedHookMsg,
+    QueryMsg, RolesResponse, RoundDataResponse, SubscriberResponse, TwapResponse,
+};
+use crate::state::{
+    CircuitBreaker, ManualRound, QuarantinedAnswer, RetentionPolicy, Role, RoundData, Subscription,
+    CIRCUIT_BREAKER, CONFIG, MANUAL_ROUNDS, PRICE_FEED_INFO, QUARANTINED_ANSWER, RETENTION_POLICY,
+    ROLES, ROUND_COUNT, ROUND_DATA, ROUND_IDS_BY_HEIGHT, ROUND_IDS_BY_TIME, ROUND_INDEX_BACKFILL,
+    SUBSCRIBERS,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:price-feed";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-// const MAX_ROUND_DATA_LEN: usize = 105120; // we will update answer every 5 minutes, so 105120 = 365 * 24 * 12
-const MAX_DIFF_ROUND_ID: u64 = 60; // the max height diff between 2 round ids, 60 = 12 * 5
+pub const DEFAULT_MAX_ROUNDS: u64 = 105120; // we will update answer every 5 minutes, so 105120 = 365 * 24 * 12
+const MAX_PRUNED_ROUNDS_PER_UPDATE: u32 = 5; // the max number of old rounds removed in each update
+const AGGREGATOR_VERSION: u64 = 1; // the version of the AggregatorV3 compatible queries
+const MAX_SUBSCRIBERS: usize = 10; // the max number of subscribers called back on each new round
+const PRICE_UPDATED_REPLY_ID: u64 = 1; // the reply id of the failed PriceUpdated callbacks
+pub const MAX_TWAP_ROUNDS: usize = 500; // the max number of rounds scanned by a TWAP query
+const DEFAULT_MIGRATION_LIMIT: u32 = 1000; // the default number of old rounds indexed by each migration
+
+// the default and max number of rounds or subscribers returned by the Rounds and Subscribers queries
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 /// Handling contract instantiation
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
-    env: Env,
+    _env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    // the sender is the first owner
+    initialize_owner(deps.storage, deps.api, info.sender.as_str())?;
+
     // init config
     let config = crate::state::Config {
-        owner: info.sender.clone(),
-        controller: deps.api.addr_validate(&msg.controller)?,
+        force_disabled: false,
     };
     CONFIG.save(deps.storage, &config)?;
 
-    // init price feed info
+    // the controller is the first writer
+    let controller = deps.api.addr_validate(&msg.controller)?;
+    ROLES.save(
+        deps.storage,
+        (Role::Writer.as_str(), &controller),
+        &Empty {},
+    )?;
+
+    // init price feed info, the first round id is 1
     let price_feed_info = crate::state::PriceFeedInfo {
-        latest_round: env.block.height,
+        latest_round: 0,
         decimals: msg.decimals,
         description: msg.description,
+        heartbeat: msg.heartbeat,
     };
     PRICE_FEED_INFO.save(deps.storage, &price_feed_info)?;
 
+    // init retention policy of the round data
+    let retention_policy = msg.retention_policy.unwrap_or(RetentionPolicy {
+        max_rounds: Some(DEFAULT_MAX_ROUNDS),
+        max_age: None,
+    });
+    validate_retention_policy(&retention_policy)?;
+    RETENTION_POLICY.save(deps.storage, &retention_policy)?;
+    ROUND_COUNT.save(deps.storage, &0)?;
+
+    // init the circuit breaker, which never trips by default
+    let circuit_breaker = msg.circuit_breaker.unwrap_or_default();
+    if !circuit_breaker.is_valid() {
+        return Err(ContractError::InvalidCircuitBreaker {});
+    }
+    CIRCUIT_BREAKER.save(deps.storage, &circuit_breaker)?;
+
     Ok(Response::new()
         .add_attribute("method", "instantiate")
         .add_attribute("owner", info.sender)
@@ -57,46 +109,209 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::UpdateController { controller } => {
-            update_controller(deps, env, info, controller)
-        }
+        ExecuteMsg::AddRole { role, address } => add_role(deps, env, info, role, address),
+        ExecuteMsg::RemoveRole { role, address } => remove_role(deps, env, info, role, address),
         ExecuteMsg::UpdateRoundData { answer } => update_round_data(deps, env, info, answer),
+        ExecuteMsg::UpdateHeartbeat { heartbeat } => update_heartbeat(deps, env, info, heartbeat),
+        ExecuteMsg::UpdateRetentionPolicy { retention_policy } => {
+            update_retention_policy(deps, env, info, retention_policy)
+        }
+        ExecuteMsg::PruneHistory { limit } => prune_history(deps, env, info, limit),
+        ExecuteMsg::ForceRoundData { answer, reason } => {
+            force_round_data(deps, env, info, answer, reason)
+        }
+        ExecuteMsg::DisableForceRoundData {} => disable_force_round_data(deps, env, info),
+        ExecuteMsg::UpdateCircuitBreaker { circuit_breaker } => {
+            update_circuit_breaker(deps, env, info, circuit_breaker)
+        }
+        ExecuteMsg::ResetCircuitBreaker { accept } => {
+            reset_circuit_breaker(deps, env, info, accept)
+        }
+        ExecuteMsg::AddSubscriber {
+            subscriber,
+            gas_limit,
+        } => add_subscriber(deps, env, info, subscriber, gas_limit),
+        ExecuteMsg::UpdateSubscriber {
+            subscriber,
+            gas_limit,
+        } => update_subscriber(deps, env, info, subscriber, gas_limit),
+        ExecuteMsg::RemoveSubscriber { subscriber } => {
+            remove_subscriber(deps, env, info, subscriber)
+        }
+        ExecuteMsg::ProposeNewOwner { new_owner, expiry } => {
+            Ok(propose_new_owner(deps, &env, &info, new_owner, expiry)?)
+        }
+        ExecuteMsg::AcceptOwnership {} => Ok(accept_ownership(deps, &env, &info)?),
+        ExecuteMsg::RenounceOwnership {} => Ok(renounce_ownership(deps, &env, &info)?),
     }
 }
 
+/// Handling submessage reply.
+/// For more info on submessage and reply, see https://github.com/CosmWasm/cosmwasm/blob/main/SEMANTICS.md#submessages
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    // only the failed PriceUpdated callbacks are replied
+    if msg.id != PRICE_UPDATED_REPLY_ID {
+        return Err(ContractError::UnknownReplyId { id: msg.id });
+    }
+
+    // the failure of a subscriber is ignored, so it does not revert the new round
+    let error = match msg.result {
+        SubMsgResult::Err(error) => error,
+        SubMsgResult::Ok(_) => String::new(),
+    };
+    Ok(Response::new()
+        .add_event(Event::new("price_updated_failed").add_attribute("error", error))
+        .add_attribute("method", "reply"))
+}
+
+/// Handling contract migration
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    // only an older or the same version of the price feed can be migrated
+    let previous = get_contract_version(deps.storage)?;
+    if previous.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigration {
+            contract: previous.contract,
+            version: previous.version,
+        });
+    }
+    let previous_version: Version = previous.version.parse()?;
+    if previous_version > CONTRACT_VERSION.parse::<Version>()? {
+        return Err(ContractError::CannotDowngrade {
+            version: previous.version,
+        });
+    }
+
+    // transform the state of each previous version in order
+    if previous_version < Version::new(0, 2, 0) {
+        migrate_from_v0_1(deps.branch())?;
+    }
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // the rounds of v0.1 are indexed in batches, the migration is repeated until it is finished
+    let (indexed_rounds, backfill_finished) =
+        backfill_round_indexes(deps.storage, msg.limit.unwrap_or(DEFAULT_MIGRATION_LIMIT))?;
+
+    Ok(Response::new().add_attributes([
+        ("method", "migrate"),
+        ("previous_version", previous.version.as_str()),
+        ("version", CONTRACT_VERSION),
+        ("indexed_rounds", &indexed_rounds.to_string()),
+        ("backfill_finished", &backfill_finished.to_string()),
+    ]))
+}
+
 /// Handling contract query
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
+        QueryMsg::Ownership {} => to_binary(&get_ownership(deps.storage)?),
         QueryMsg::LastestRoundData {} => to_binary(&query_lastest_round_data(deps, env)?),
+        QueryMsg::LatestFreshRoundData { max_age } => {
+            to_binary(&query_latest_fresh_round_data(deps, env, max_age)?)
+        }
         QueryMsg::RoundData { round_id } => to_binary(&query_round_data(deps, round_id)?),
+        QueryMsg::Rounds {
+            start_after,
+            limit,
+            order,
+        } => to_binary(&query_rounds(deps, start_after, limit, order)?),
+        QueryMsg::RoundAtHeight { height } => {
+            to_binary(&query_round_at(deps, &ROUND_IDS_BY_HEIGHT, height)?)
+        }
+        QueryMsg::RoundAtTime { time } => {
+            to_binary(&query_round_at(deps, &ROUND_IDS_BY_TIME, time.seconds())?)
+        }
         QueryMsg::Decimals {} => to_binary(&PRICE_FEED_INFO.load(deps.storage)?.decimals),
         QueryMsg::Description {} => to_binary(&PRICE_FEED_INFO.load(deps.storage)?.description),
-        QueryMsg::Controller {} => to_binary(&CONFIG.load(deps.storage)?.controller),
+        QueryMsg::Roles {} => to_binary(&query_roles(deps)?),
+        QueryMsg::ManualRound { round_id } => {
+            to_binary(&MANUAL_ROUNDS.may_load(deps.storage, round_id)?)
+        }
+        QueryMsg::Heartbeat {} => to_binary(&PRICE_FEED_INFO.load(deps.storage)?.heartbeat),
+        QueryMsg::RetentionPolicy {} => to_binary(&RETENTION_POLICY.load(deps.storage)?),
+        QueryMsg::CircuitBreaker {} => to_binary(&CIRCUIT_BREAKER.load(deps.storage)?),
+        QueryMsg::CircuitOpen {} => to_binary(&QUARANTINED_ANSWER.exists(deps.storage)),
+        QueryMsg::QuarantinedAnswer {} => to_binary(&QUARANTINED_ANSWER.may_load(deps.storage)?),
+        QueryMsg::Subscribers { start_after, limit } => {
+            to_binary(&query_subscribers(deps, start_after, limit)?)
+        }
+        QueryMsg::Twap {
+            start_round,
+            end_round,
+        } => to_binary(&query_twap(deps, env, start_round, end_round)?),
+        QueryMsg::TwapWindow { seconds } => to_binary(&query_twap_window(deps, env, seconds)?),
+        QueryMsg::LatestRoundData {} => to_binary(&query_aggregator_latest_round_data(deps)?),
+        QueryMsg::GetRoundData { round_id } => {
+            to_binary(&query_aggregator_round_data(deps, round_id)?)
+        }
+        QueryMsg::Version {} => to_binary(&AGGREGATOR_VERSION),
     }
 }
 
-pub fn update_controller(
+pub fn add_role(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    controller: String,
+    role: Role,
+    address: String,
 ) -> Result<Response, ContractError> {
     // check if the sender is the owner
-    let config = crate::state::CONFIG.load(deps.storage)?;
-    if info.sender != config.owner {
+    if !is_owner(deps.storage, &info.sender)? {
         return Err(ContractError::Unauthorized {});
     }
 
-    // update the controller
-    let mut config = crate::state::CONFIG.load(deps.storage)?;
-    config.controller = deps.api.addr_validate(&controller)?;
-    crate::state::CONFIG.save(deps.storage, &config)?;
+    let address = deps.api.addr_validate(&address)?;
+    if ROLES.has(deps.storage, (role.as_str(), &address)) {
+        return Err(ContractError::RoleAlreadyGranted {});
+    }
+    ROLES.save(deps.storage, (role.as_str(), &address), &Empty {})?;
 
     // return the response
-    Ok(Response::new()
-        .add_attribute("method", "update_controller")
-        .add_attribute("controller", controller))
+    Ok(Response::new().add_attributes([
+        ("method", "add_role"),
+        ("role", role.as_str()),
+        ("address", address.as_str()),
+    ]))
+}
+
+pub fn remove_role(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let address = deps.api.addr_validate(&address)?;
+    if !ROLES.has(deps.storage, (role.as_str(), &address)) {
+        return Err(ContractError::RoleNotGranted {});
+    }
+    ROLES.remove(deps.storage, (role.as_str(), &address));
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "remove_role"),
+        ("role", role.as_str()),
+        ("address", address.as_str()),
+    ]))
+}
+
+// check if the sender is the owner or has any of the roles
+fn check_role(storage: &dyn Storage, sender: &Addr, roles: &[Role]) -> Result<(), ContractError> {
+    if is_owner(storage, sender)?
+        || roles
+            .iter()
+            .any(|role| ROLES.has(storage, (role.as_str(), sender)))
+    {
+        return Ok(());
+    }
+    Err(ContractError::Unauthorized {})
 }
 
 pub fn update_round_data(
@@ -105,23 +320,293 @@ pub fn update_round_data(
     info: MessageInfo,
     answer: u64,
 ) -> Result<Response, ContractError> {
-    // check if the sender is the controller
-    let config = crate::state::CONFIG.load(deps.storage)?;
-    if info.sender != config.controller {
+    // check if the sender is a writer, the owner cannot update the round data
+    if !ROLES.has(deps.storage, (Role::Writer.as_str(), &info.sender)) {
         return Err(ContractError::Unauthorized {});
     }
 
-    // // TODO: Optimize the code to reduce the gas cost
-    // // remove the first round data if the round data length is greater than 105120
-    // let round_data_len = ROUND_DATA
-    //     .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-    //     .collect::<StdResult<Vec<_>>>()?;
-    // if round_data_len.len() > MAX_ROUND_DATA_LEN {
-    //     ROUND_DATA.remove(deps.storage, round_data_len[0].0);
-    // }
+    // the answer which trips the circuit breaker is quarantined instead of being saved,
+    // and all answers are quarantined until the owner or an admin resets the circuit breaker
+    let circuit_open = QUARANTINED_ANSWER.exists(deps.storage);
+    let latest_answer = ROUND_DATA
+        .may_load(
+            deps.storage,
+            PRICE_FEED_INFO.load(deps.storage)?.latest_round,
+        )?
+        .map(|round_data| round_data.answer);
+    if circuit_open
+        || CIRCUIT_BREAKER
+            .load(deps.storage)?
+            .trips(Uint128::from(answer), latest_answer)
+    {
+        QUARANTINED_ANSWER.save(
+            deps.storage,
+            &QuarantinedAnswer {
+                answer: Uint128::from(answer),
+                updated_at: env.block.time,
+            },
+        )?;
 
-    // load the latest round id from the price feed info
-    let latest_round_id = env.block.height;
+        let mut res = Response::new();
+        if !circuit_open {
+            res = res.add_event(
+                Event::new("circuit_breaker_tripped").add_attribute("answer", answer.to_string()),
+            );
+        }
+        return Ok(res.add_attributes([
+            ("method", "update_round_data"),
+            ("answer", &answer.to_string()),
+            ("circuit_open", "true"),
+        ]));
+    }
+
+    let (latest_round_id, pruned) = save_round_data(deps.storage, &env, answer)?;
+
+    // return the response
+    Ok(Response::new()
+        .add_submessages(price_updated_msgs(deps.storage, latest_round_id, answer)?)
+        .add_attribute("method", "update_round_data")
+        .add_attribute("round_id", latest_round_id.to_string())
+        .add_attribute("answer", answer.to_string())
+        .add_attribute("pruned", pruned.to_string()))
+}
+
+pub fn force_round_data(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    answer: u64,
+    reason: String,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner, an admin or a guardian
+    check_role(deps.storage, &info.sender, &[Role::Admin, Role::Guardian])?;
+    if CONFIG.load(deps.storage)?.force_disabled {
+        return Err(ContractError::ForceRoundDataDisabled {});
+    }
+    if reason.trim().is_empty() {
+        return Err(ContractError::InvalidReason {});
+    }
+
+    let (round_id, pruned) = save_round_data(deps.storage, &env, answer)?;
+
+    // keep the audit trail of the manual round
+    MANUAL_ROUNDS.save(
+        deps.storage,
+        round_id,
+        &ManualRound {
+            forced_by: info.sender.clone(),
+            reason: reason.clone(),
+        },
+    )?;
+
+    // return the response
+    Ok(Response::new()
+        .add_submessages(price_updated_msgs(deps.storage, round_id, answer)?)
+        .add_event(Event::new("force_round_data").add_attributes([
+            ("round_id", round_id.to_string()),
+            ("answer", answer.to_string()),
+            ("forced_by", info.sender.to_string()),
+            ("reason", reason),
+        ]))
+        .add_attribute("method", "force_round_data")
+        .add_attribute("pruned", pruned.to_string()))
+}
+
+pub fn disable_force_round_data(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // there is no way to enable it again
+    config.force_disabled = true;
+    CONFIG.save(deps.storage, &config)?;
+
+    // return the response
+    Ok(Response::new().add_attribute("method", "disable_force_round_data"))
+}
+
+pub fn update_circuit_breaker(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    circuit_breaker: CircuitBreaker,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner or an admin
+    check_role(deps.storage, &info.sender, &[Role::Admin])?;
+
+    if !circuit_breaker.is_valid() {
+        return Err(ContractError::InvalidCircuitBreaker {});
+    }
+    CIRCUIT_BREAKER.save(deps.storage, &circuit_breaker)?;
+
+    // return the response
+    Ok(Response::new().add_attribute("method", "update_circuit_breaker"))
+}
+
+pub fn reset_circuit_breaker(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    accept: bool,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner or an admin
+    check_role(deps.storage, &info.sender, &[Role::Admin])?;
+
+    let quarantined_answer = QUARANTINED_ANSWER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::CircuitClosed {})?;
+    QUARANTINED_ANSWER.remove(deps.storage);
+
+    // the accepted answer is saved as the round data of the current block
+    let mut res = Response::new().add_attributes([
+        ("method", "reset_circuit_breaker"),
+        ("accept", &accept.to_string()),
+    ]);
+    if accept {
+        let answer = quarantined_answer.answer.u128() as u64;
+        let (round_id, pruned) = save_round_data(deps.storage, &env, answer)?;
+        res = res
+            .add_submessages(price_updated_msgs(deps.storage, round_id, answer)?)
+            .add_attributes([
+                ("round_id", round_id.to_string()),
+                ("answer", quarantined_answer.answer.to_string()),
+                ("pruned", pruned.to_string()),
+            ]);
+    }
+    Ok(res)
+}
+
+// the PriceUpdated callbacks of the new round, each subscriber is called with its own gas limit
+// and its failure is caught by the reply
+fn price_updated_msgs(storage: &dyn Storage, round_id: u64, answer: u64) -> StdResult<Vec<SubMsg>> {
+    let msg = to_binary(&PriceFeedHookMsg::PriceUpdated {
+        round_id,
+        answer: Uint128::from(answer),
+    })?;
+
+    SUBSCRIBERS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (subscriber, subscription) = item?;
+            Ok(SubMsg {
+                id: PRICE_UPDATED_REPLY_ID,
+                msg: CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: subscriber.to_string(),
+                    msg: msg.clone(),
+                    funds: vec![],
+                }),
+                gas_limit: Some(subscription.gas_limit),
+                reply_on: ReplyOn::Error,
+            })
+        })
+        .collect()
+}
+
+pub fn add_subscriber(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    subscriber: String,
+    gas_limit: u64,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner or an admin
+    check_role(deps.storage, &info.sender, &[Role::Admin])?;
+
+    let subscriber = deps.api.addr_validate(&subscriber)?;
+    if SUBSCRIBERS.has(deps.storage, subscriber.clone()) {
+        return Err(ContractError::SubscriberAlreadyExists {});
+    }
+    // the writers pay the gas of all callbacks, so the number of subscribers is limited
+    if SUBSCRIBERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count()
+        >= MAX_SUBSCRIBERS
+    {
+        return Err(ContractError::TooManySubscribers {});
+    }
+    if gas_limit == 0 {
+        return Err(ContractError::InvalidGasLimit {});
+    }
+    SUBSCRIBERS.save(
+        deps.storage,
+        subscriber.clone(),
+        &Subscription { gas_limit },
+    )?;
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "add_subscriber"),
+        ("subscriber", subscriber.as_str()),
+        ("gas_limit", &gas_limit.to_string()),
+    ]))
+}
+
+pub fn update_subscriber(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    subscriber: String,
+    gas_limit: u64,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner or an admin
+    check_role(deps.storage, &info.sender, &[Role::Admin])?;
+
+    let subscriber = deps.api.addr_validate(&subscriber)?;
+    if !SUBSCRIBERS.has(deps.storage, subscriber.clone()) {
+        return Err(ContractError::SubscriberNotFound {});
+    }
+    if gas_limit == 0 {
+        return Err(ContractError::InvalidGasLimit {});
+    }
+    SUBSCRIBERS.save(
+        deps.storage,
+        subscriber.clone(),
+        &Subscription { gas_limit },
+    )?;
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "update_subscriber"),
+        ("subscriber", subscriber.as_str()),
+        ("gas_limit", &gas_limit.to_string()),
+    ]))
+}
+
+pub fn remove_subscriber(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    subscriber: String,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner, an admin or the subscriber itself
+    let subscriber = deps.api.addr_validate(&subscriber)?;
+    if info.sender != subscriber {
+        check_role(deps.storage, &info.sender, &[Role::Admin])?;
+    }
+
+    if !SUBSCRIBERS.has(deps.storage, subscriber.clone()) {
+        return Err(ContractError::SubscriberNotFound {});
+    }
+    SUBSCRIBERS.remove(deps.storage, subscriber.clone());
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "remove_subscriber"),
+        ("subscriber", subscriber.as_str()),
+    ]))
+}
+
+// save the answer as the round data of the current block,
+// returns the round id and the number of pruned rounds
+fn save_round_data(storage: &mut dyn Storage, env: &Env, answer: u64) -> StdResult<(u64, u32)> {
+    let mut price_feed_info = PRICE_FEED_INFO.load(storage)?;
 
     // lastest round data
     let round_data = RoundData {
@@ -129,52 +614,448 @@ pub fn update_round_data(
         updated_at: env.block.time,
     };
 
-    // save the round data
-    ROUND_DATA.save(deps.storage, latest_round_id, &round_data)?;
+    // a round updated twice in the same block keeps its round id and is not counted again,
+    // otherwise the round id follows the latest round id
+    let latest_round_id = match ROUND_IDS_BY_HEIGHT.may_load(storage, env.block.height)? {
+        Some(round_id) if round_id == price_feed_info.latest_round => round_id,
+        _ => {
+            ROUND_COUNT.update(storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+            price_feed_info.latest_round + 1
+        }
+    };
+    ROUND_DATA.save(storage, latest_round_id, &round_data)?;
+    ROUND_IDS_BY_HEIGHT.save(storage, env.block.height, &latest_round_id)?;
+    ROUND_IDS_BY_TIME.save(storage, env.block.time.seconds(), &latest_round_id)?;
+
+    // a round updated by the controller after it is forced in the same block is not manual anymore
+    MANUAL_ROUNDS.remove(storage, latest_round_id);
 
     // update the latest round id in the price feed info
-    let mut price_feed_info = PRICE_FEED_INFO.load(deps.storage)?;
     price_feed_info.latest_round = latest_round_id;
+    PRICE_FEED_INFO.save(storage, &price_feed_info)?;
+
+    // remove a few of the oldest rounds which are out of the retention policy
+    let pruned = prune_round_data(storage, env.block.time, MAX_PRUNED_ROUNDS_PER_UPDATE)?;
+    Ok((latest_round_id, pruned))
+}
+
+pub fn update_heartbeat(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    heartbeat: Option<u64>,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner or an admin
+    check_role(deps.storage, &info.sender, &[Role::Admin])?;
+
+    // update the heartbeat in the price feed info
+    let mut price_feed_info = PRICE_FEED_INFO.load(deps.storage)?;
+    price_feed_info.heartbeat = heartbeat;
     PRICE_FEED_INFO.save(deps.storage, &price_feed_info)?;
 
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "update_heartbeat"),
+        (
+            "heartbeat",
+            &heartbeat.map_or("none".to_string(), |heartbeat| heartbeat.to_string()),
+        ),
+    ]))
+}
+
+pub fn update_retention_policy(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    retention_policy: RetentionPolicy,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner or an admin
+    check_role(deps.storage, &info.sender, &[Role::Admin])?;
+
+    // update the retention policy, the old rounds are pruned by the next updates or by PruneHistory
+    validate_retention_policy(&retention_policy)?;
+    RETENTION_POLICY.save(deps.storage, &retention_policy)?;
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "update_retention_policy"),
+        (
+            "max_rounds",
+            &retention_policy
+                .max_rounds
+                .map_or("none".to_string(), |max_rounds| max_rounds.to_string()),
+        ),
+        (
+            "max_age",
+            &retention_policy
+                .max_age
+                .map_or("none".to_string(), |max_age| max_age.to_string()),
+        ),
+    ]))
+}
+
+pub fn prune_history(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: u32,
+) -> Result<Response, ContractError> {
+    // check if the sender is the owner or an admin
+    check_role(deps.storage, &info.sender, &[Role::Admin])?;
+
+    let pruned = prune_round_data(deps.storage, env.block.time, limit)?;
+
     // return the response
     Ok(Response::new()
-        .add_attribute("method", "update_round_data")
-        .add_attribute("round_id", latest_round_id.to_string())
-        .add_attribute("answer", answer.to_string()))
+        .add_attribute("method", "prune_history")
+        .add_attribute("pruned", pruned.to_string()))
 }
 
-pub fn query_lastest_round_data(deps: Deps, env: Env) -> StdResult<RoundDataResponse> {
+fn validate_retention_policy(retention_policy: &RetentionPolicy) -> Result<(), ContractError> {
+    // the latest round is always kept, so keeping zero rounds or rounds of zero seconds makes no sense
+    if retention_policy.max_rounds == Some(0) || retention_policy.max_age == Some(0) {
+        return Err(ContractError::InvalidRetentionPolicy {});
+    }
+    Ok(())
+}
+
+// remove at most `limit` of the oldest rounds which exceed the retention policy,
+// the latest round is never removed
+fn prune_round_data(storage: &mut dyn Storage, now: Timestamp, limit: u32) -> StdResult<u32> {
+    // the rounds of v0.1 are not counted until the migration indexes all of them
+    if ROUND_INDEX_BACKFILL.exists(storage) {
+        return Ok(0);
+    }
+
+    let retention_policy = RETENTION_POLICY.load(storage)?;
+    let latest_round_id = PRICE_FEED_INFO.load(storage)?.latest_round;
+    let mut round_count = ROUND_COUNT.load(storage)?;
+
+    let oldest_rounds = ROUND_DATA
+        .range(storage, None, None, Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut pruned = 0;
+    for (round_id, round_data) in oldest_rounds {
+        if round_id == latest_round_id {
+            break;
+        }
+
+        let too_many = retention_policy
+            .max_rounds
+            .map_or(false, |max_rounds| round_count > max_rounds);
+        let too_old = retention_policy.max_age.map_or(false, |max_age| {
+            round_data.updated_at.plus_seconds(max_age) < now
+        });
+        if !too_many && !too_old {
+            break;
+        }
+
+        ROUND_DATA.remove(storage, round_id);
+        round_count -= 1;
+        pruned += 1;
+    }
+    ROUND_COUNT.save(storage, &round_count)?;
+
+    // the indexes are sorted like the round ids, so the entries of the pruned rounds come first
+    if pruned > 0 {
+        let oldest_round_id = ROUND_DATA
+            .keys(storage, None, None, Order::Ascending)
+            .next()
+            .transpose()?
+            .unwrap_or(latest_round_id);
+        for index in [&ROUND_IDS_BY_HEIGHT, &ROUND_IDS_BY_TIME] {
+            let pruned_keys = index
+                .range(storage, None, None, Order::Ascending)
+                .take_while(|item| {
+                    item.as_ref()
+                        .map_or(true, |(_, round_id)| *round_id < oldest_round_id)
+                })
+                .map(|item| item.map(|(key, _)| key))
+                .collect::<StdResult<Vec<_>>>()?;
+            for key in pruned_keys {
+                index.remove(storage, key);
+            }
+        }
+    }
+
+    Ok(pruned)
+}
+
+pub fn query_lastest_round_data(deps: Deps, _env: Env) -> StdResult<RoundDataResponse> {
     // load the latest round id from the price feed info
     let latest_round_id = PRICE_FEED_INFO.load(deps.storage)?.latest_round;
-    // load the round data from the round data map
-    let lastest_round_data = ROUND_DATA.load(deps.storage, latest_round_id)?;
 
-    let res = RoundDataResponse {
-        round_id: env.block.height,
-        answer: lastest_round_data.answer,
-        updated_at: lastest_round_data.updated_at,
-        answered_in_round: latest_round_id,
+    query_round_data(deps, latest_round_id)
+}
+
+pub fn query_latest_fresh_round_data(
+    deps: Deps,
+    env: Env,
+    max_age: Option<u64>,
+) -> StdResult<RoundDataResponse> {
+    let lastest_round_data = query_lastest_round_data(deps, env.clone())?;
+
+    // use the heartbeat of the price feed if the caller does not set the max age
+    let max_age = match max_age {
+        Some(max_age) => Some(max_age),
+        None => PRICE_FEED_INFO.load(deps.storage)?.heartbeat,
     };
-    // return the round data
-    Ok(res)
+    if let Some(max_age) = max_age {
+        if lastest_round_data.is_stale(max_age, env.block.time) {
+            return Err(StdError::generic_err(format!(
+                "Stale price, updated at {}",
+                lastest_round_data.updated_at
+            )));
+        }
+    }
+
+    Ok(lastest_round_data)
 }
 
 pub fn query_round_data(deps: Deps, round_id: u64) -> StdResult<RoundDataResponse> {
-    let min: Option<Bound<u64>> = Some(Bound::inclusive(round_id - MAX_DIFF_ROUND_ID * 13)); // query data in previous 1 hour 5 minutes
-    let max: Option<Bound<u64>> = Some(Bound::inclusive(round_id));
     // load the round data from the round data map
-    let binding = ROUND_DATA
-        .range(deps.storage, min, max, cosmwasm_std::Order::Ascending)
-        .collect::<StdResult<Vec<_>>>()?;
-    let round_data = binding.last().unwrap();
+    let round_data = ROUND_DATA.load(deps.storage, round_id)?;
+
+    Ok(round_data_response(round_id, round_data))
+}
+
+pub fn query_roles(deps: Deps) -> StdResult<RolesResponse> {
+    let members = |role: Role| -> StdResult<Vec<String>> {
+        ROLES
+            .prefix(role.as_str())
+            .keys(deps.storage, None, None, Order::Ascending)
+            .map(|address| Ok(address?.to_string()))
+            .collect()
+    };
+
+    Ok(RolesResponse {
+        owner: get_ownership(deps.storage)?
+            .owner
+            .map(|owner| owner.to_string()),
+        admins: members(Role::Admin)?,
+        writers: members(Role::Writer)?,
+        guardians: members(Role::Guardian)?,
+    })
+}
+
+pub fn query_subscribers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<SubscriberResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = match start_after {
+        Some(start_after) => Some(Bound::exclusive(deps.api.addr_validate(&start_after)?)),
+        None => None,
+    };
+
+    SUBSCRIBERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (address, subscription): (Addr, Subscription) = item?;
+            Ok(SubscriberResponse {
+                address: address.to_string(),
+                gas_limit: subscription.gas_limit,
+            })
+        })
+        .collect()
+}
+
+pub fn query_rounds(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+    order: Option<OrderBy>,
+) -> StdResult<Vec<RoundDataResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let order: Order = order.unwrap_or(OrderBy::Ascending).into();
+
+    // the page starts after `start_after` in the direction of the order
+    let start_after = start_after.map(Bound::exclusive);
+    let (min, max) = match order {
+        Order::Ascending => (start_after, None),
+        Order::Descending => (None, start_after),
+    };
+
+    ROUND_DATA
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|item| {
+            let (round_id, round_data) = item?;
+            Ok(round_data_response(round_id, round_data))
+        })
+        .collect()
+}
+
+// find the latest round saved at or before the key of the index
+pub fn query_round_at(
+    deps: Deps,
+    index: &Map<u64, u64>,
+    key: u64,
+) -> StdResult<Option<RoundDataResponse>> {
+    let round_id = match index
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::inclusive(key)),
+            Order::Descending,
+        )
+        .next()
+    {
+        Some(item) => item?.1,
+        None => return Ok(None),
+    };
+
+    Ok(ROUND_DATA
+        .may_load(deps.storage, round_id)?
+        .map(|round_data| round_data_response(round_id, round_data)))
+}
+
+fn round_data_response(round_id: u64, round_data: RoundData) -> RoundDataResponse {
+    RoundDataResponse {
+        round_id,
+        answer: round_data.answer,
+        updated_at: round_data.updated_at,
+        answered_in_round: round_id,
+    }
+}
 
-    let res = RoundDataResponse {
+pub fn query_aggregator_latest_round_data(deps: Deps) -> StdResult<AggregatorRoundDataResponse> {
+    let latest_round_id = PRICE_FEED_INFO.load(deps.storage)?.latest_round;
+
+    query_aggregator_round_data(deps, latest_round_id)
+}
+
+pub fn query_aggregator_round_data(
+    deps: Deps,
+    round_id: u64,
+) -> StdResult<AggregatorRoundDataResponse> {
+    let round_data = ROUND_DATA.load(deps.storage, round_id)?;
+
+    Ok(AggregatorRoundDataResponse {
         round_id,
-        answer: round_data.1.answer,
-        updated_at: round_data.1.updated_at,
-        answered_in_round: round_data.0,
+        answer: round_data.answer,
+        started_at: round_data.updated_at,
+        updated_at: round_data.updated_at,
+        answered_in_round: round_id,
+    })
+}
+
+pub fn query_twap(
+    deps: Deps,
+    env: Env,
+    start_round: u64,
+    end_round: u64,
+) -> StdResult<TwapResponse> {
+    if start_round > end_round {
+        return Err(StdError::generic_err(
+            "start_round must not be greater than end_round",
+        ));
+    }
+
+    // load all rounds in the range, the range cannot include too many rounds
+    let rounds = ROUND_DATA
+        .range(
+            deps.storage,
+            Some(Bound::inclusive(start_round)),
+            Some(Bound::inclusive(end_round)),
+            Order::Ascending,
+        )
+        .take(MAX_TWAP_ROUNDS + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    if rounds.is_empty() {
+        return Err(StdError::not_found("RoundData"));
+    }
+    if rounds.len() > MAX_TWAP_ROUNDS {
+        return Err(StdError::generic_err(format!(
+            "the range must not include more than {MAX_TWAP_ROUNDS} rounds"
+        )));
+    }
+    let last_round_id = rounds[rounds.len() - 1].0;
+
+    // the last round of the range stays current until the next round is updated, or until now
+    let end_at = match ROUND_DATA
+        .range(
+            deps.storage,
+            Some(Bound::exclusive(last_round_id)),
+            None,
+            Order::Ascending,
+        )
+        .next()
+    {
+        Some(next_round) => next_round?.1.updated_at,
+        None => env.block.time,
     };
-    // return the round data
-    Ok(res)
+
+    time_weighted_average(&rounds, rounds[0].1.updated_at, end_at)
+}
+
+pub fn query_twap_window(deps: Deps, env: Env, seconds: u64) -> StdResult<TwapResponse> {
+    let end_at = env.block.time;
+    let start_at = Timestamp::from_seconds(end_at.seconds().saturating_sub(seconds));
+
+    // walk back from the latest round until we reach the round which was current at the start of the window,
+    // the window cannot include too many rounds
+    let mut rounds = vec![];
+    for item in ROUND_DATA.range(deps.storage, None, None, Order::Descending) {
+        if rounds.len() == MAX_TWAP_ROUNDS {
+            return Err(StdError::generic_err(format!(
+                "the window must not include more than {MAX_TWAP_ROUNDS} rounds"
+            )));
+        }
+        let (round_id, round_data) = item?;
+        let updated_at = round_data.updated_at;
+        rounds.push((round_id, round_data));
+        if updated_at <= start_at {
+            break;
+        }
+    }
+    if rounds.is_empty() {
+        return Err(StdError::not_found("RoundData"));
+    }
+    rounds.reverse();
+
+    time_weighted_average(&rounds, start_at, end_at)
+}
+
+// weight the answer of each round by the number of seconds it stayed current between `start_at` and `end_at`,
+// the rounds must be sorted by round id in ascending order
+fn time_weighted_average(
+    rounds: &[(u64, RoundData)],
+    start_at: Timestamp,
+    end_at: Timestamp,
+) -> StdResult<TwapResponse> {
+    let mut weighted_sum = Uint128::zero();
+    let mut total_weight = 0u64;
+    for (i, (_, round_data)) in rounds.iter().enumerate() {
+        let current_from = round_data.updated_at.max(start_at);
+        let current_to = rounds
+            .get(i + 1)
+            .map_or(end_at, |(_, next_round)| next_round.updated_at);
+        let weight = current_to.seconds().saturating_sub(current_from.seconds());
+
+        weighted_sum = weighted_sum.checked_add(round_data.answer.checked_mul(weight.into())?)?;
+        total_weight += weight;
+    }
+
+    let (start_round, first_round) = &rounds[0];
+    let (end_round, last_round) = &rounds[rounds.len() - 1];
+
+    // if no time has passed, the answer of the last round is the average
+    let answer = if total_weight == 0 {
+        last_round.answer
+    } else {
+        weighted_sum.checked_div(total_weight.into())?
+    };
+
+    Ok(TwapResponse {
+        answer,
+        start_round: *start_round,
+        end_round: *end_round,
+        start_at: first_round.updated_at.max(start_at),
+        end_at,
+    })
 }
@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use ownership::OwnershipError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,6 +7,12 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("{0}")]
+    SemVer(#[from] semver::Error),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -13,4 +20,45 @@ pub enum ContractError {
     CustomError { val: String },
     // Add any other custom errors you like here.
     // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
+    #[error("Invalid Retention Policy")]
+    InvalidRetentionPolicy {},
+
+    #[error("Force Round Data Disabled")]
+    ForceRoundDataDisabled {},
+
+    #[error("Invalid Reason")]
+    InvalidReason {},
+
+    #[error("Invalid Circuit Breaker")]
+    InvalidCircuitBreaker {},
+
+    #[error("Circuit Closed")]
+    CircuitClosed {},
+
+    #[error("Subscriber Already Exists")]
+    SubscriberAlreadyExists {},
+
+    #[error("Subscriber Not Found")]
+    SubscriberNotFound {},
+
+    #[error("Too Many Subscribers")]
+    TooManySubscribers {},
+
+    #[error("Invalid Gas Limit")]
+    InvalidGasLimit {},
+
+    #[error("Unknown Reply Id {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Role Already Granted")]
+    RoleAlreadyGranted {},
+
+    #[error("Role Not Granted")]
+    RoleNotGranted {},
+
+    #[error("Cannot Migrate from {contract} {version}")]
+    InvalidMigration { contract: String, version: String },
+
+    #[error("Cannot Migrate from the Newer Version {version}")]
+    CannotDowngrade { version: String },
 }
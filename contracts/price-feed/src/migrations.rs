@@ -0,0 +1,131 @@
+use cosmwasm_std::{DepsMut, Empty, Order, StdResult, Storage};
+use cw_storage_plus::Bound;
+use ownership::initialize_owner;
+
+use crate::contract::DEFAULT_MAX_ROUNDS;
+use crate::state::{
+    CircuitBreaker, Config, PriceFeedInfo, RetentionPolicy, Role, RoundIndexBackfill,
+    CIRCUIT_BREAKER, CONFIG, PRICE_FEED_INFO, RETENTION_POLICY, ROLES, ROUND_COUNT, ROUND_DATA,
+    ROUND_IDS_BY_HEIGHT, ROUND_IDS_BY_TIME, ROUND_INDEX_BACKFILL,
+};
+
+// the state layout of v0.1, which is replaced by the current one
+pub mod v0_1 {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::Addr;
+    use cw_storage_plus::Item;
+
+    #[cw_serde]
+    pub struct Config {
+        pub owner: Addr,
+        pub controller: Addr,
+    }
+
+    #[cw_serde]
+    pub struct PriceFeedInfo {
+        pub latest_round: u64,
+        pub decimals: u8,
+        pub description: String,
+    }
+
+    pub const CONFIG: Item<Config> = Item::new("config");
+    pub const PRICE_FEED_INFO: Item<PriceFeedInfo> = Item::new("price_feed_info");
+}
+
+// migrate the state of v0.1 to the current layout
+pub fn migrate_from_v0_1(deps: DepsMut) -> StdResult<()> {
+    // the owner moves to the ownership and the controller becomes the first writer
+    let config = v0_1::CONFIG.load(deps.storage)?;
+    initialize_owner(deps.storage, deps.api, config.owner.as_str())?;
+    ROLES.save(
+        deps.storage,
+        (Role::Writer.as_str(), &config.controller),
+        &Empty {},
+    )?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            force_disabled: false,
+        },
+    )?;
+
+    // the rounds of v0.1 are keyed by their block height, so the sequential round ids
+    // continue from the latest height and stay increasing
+    let price_feed_info = v0_1::PRICE_FEED_INFO.load(deps.storage)?;
+    PRICE_FEED_INFO.save(
+        deps.storage,
+        &PriceFeedInfo {
+            latest_round: price_feed_info.latest_round,
+            decimals: price_feed_info.decimals,
+            description: price_feed_info.description,
+            heartbeat: None,
+        },
+    )?;
+
+    // v0.1 keeps every round and has no indexes, the rounds are indexed and counted in batches
+    // by `backfill_round_indexes`, then they are pruned with the default retention policy
+    ROUND_COUNT.save(deps.storage, &0)?;
+    ROUND_INDEX_BACKFILL.save(
+        deps.storage,
+        &RoundIndexBackfill {
+            start_after: None,
+            last_round: price_feed_info.latest_round,
+        },
+    )?;
+    RETENTION_POLICY.save(
+        deps.storage,
+        &RetentionPolicy {
+            max_rounds: Some(DEFAULT_MAX_ROUNDS),
+            max_age: None,
+        },
+    )?;
+    CIRCUIT_BREAKER.save(deps.storage, &CircuitBreaker::default())?;
+
+    Ok(())
+}
+
+// index and count at most `limit` of the rounds of v0.1, returns the number of indexed rounds
+// and whether all of them are indexed
+pub fn backfill_round_indexes(storage: &mut dyn Storage, limit: u32) -> StdResult<(u32, bool)> {
+    let mut backfill = match ROUND_INDEX_BACKFILL.may_load(storage)? {
+        Some(backfill) => backfill,
+        None => return Ok((0, true)),
+    };
+
+    let rounds = ROUND_DATA
+        .range(
+            storage,
+            backfill.start_after.map(Bound::exclusive),
+            Some(Bound::inclusive(backfill.last_round)),
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // the round ids of v0.1 are the block heights, an index entry which already exists
+    // belongs to a later round and is kept
+    for (round_id, round_data) in &rounds {
+        for (index, key) in [
+            (&ROUND_IDS_BY_HEIGHT, *round_id),
+            (&ROUND_IDS_BY_TIME, round_data.updated_at.seconds()),
+        ] {
+            index.update(storage, key, |indexed| -> StdResult<_> {
+                Ok(indexed.map_or(*round_id, |indexed| indexed.max(*round_id)))
+            })?;
+        }
+    }
+    ROUND_COUNT.update(storage, |count| -> StdResult<_> {
+        Ok(count + rounds.len() as u64)
+    })?;
+
+    // all the rounds are indexed once a batch is not full
+    let finished = rounds.len() < limit as usize;
+    if finished {
+        ROUND_INDEX_BACKFILL.remove(storage);
+    } else {
+        backfill.start_after = rounds.last().map(|(round_id, _)| *round_id);
+        ROUND_INDEX_BACKFILL.save(storage, &backfill)?;
+    }
+
+    Ok((rounds.len() as u32, finished))
+}
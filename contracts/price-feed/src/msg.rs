@@ -1,24 +1,96 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Timestamp, Uint128};
+use cosmwasm_std::{Order, Timestamp, Uint128};
+use cw_utils::Expiration;
+use ownership::Ownership;
+
+use crate::state::{CircuitBreaker, ManualRound, QuarantinedAnswer, RetentionPolicy, Role};
 
 /// Message type for `instantiate` entry_point
 #[cw_serde]
 pub struct InstantiateMsg {
+    // the first writer of the round data, usually the price collector
     pub controller: String,
     pub decimals: u8,
     pub description: String,
+    pub heartbeat: Option<u64>,
+    pub retention_policy: Option<RetentionPolicy>,
+    pub circuit_breaker: Option<CircuitBreaker>,
 }
 
 /// Message type for `execute` entry_point
 #[cw_serde]
 pub enum ExecuteMsg {
-    UpdateController { controller: String },
-    UpdateRoundData { answer: u64 },
+    // only the owner can grant or revoke the roles
+    AddRole {
+        role: Role,
+        address: String,
+    },
+    RemoveRole {
+        role: Role,
+        address: String,
+    },
+    UpdateRoundData {
+        answer: u64,
+    },
+    UpdateHeartbeat {
+        heartbeat: Option<u64>,
+    },
+    UpdateRetentionPolicy {
+        retention_policy: RetentionPolicy,
+    },
+    PruneHistory {
+        limit: u32,
+    },
+    // set the round data in an emergency, only the owner, the admins or the guardians can do it
+    ForceRoundData {
+        answer: u64,
+        reason: String,
+    },
+    // disable the ForceRoundData permanently
+    DisableForceRoundData {},
+    UpdateCircuitBreaker {
+        circuit_breaker: CircuitBreaker,
+    },
+    // close the circuit after reviewing the quarantined answer, which is saved if it is accepted
+    ResetCircuitBreaker {
+        accept: bool,
+    },
+    // the subscriber is called back with PriceUpdated on each new round
+    AddSubscriber {
+        subscriber: String,
+        gas_limit: u64,
+    },
+    UpdateSubscriber {
+        subscriber: String,
+        gas_limit: u64,
+    },
+    // the owner or the subscriber itself can remove the subscription
+    RemoveSubscriber {
+        subscriber: String,
+    },
+    // propose a new owner, who must accept the ownership before the expiry
+    ProposeNewOwner {
+        new_owner: String,
+        expiry: Option<Expiration>,
+    },
+    AcceptOwnership {},
+    // give up the ownership permanently
+    RenounceOwnership {},
+}
+
+/// Message type of the callback which the subscribers must handle in their `execute` entry_point
+#[cw_serde]
+pub enum PriceFeedHookMsg {
+    PriceUpdated { round_id: u64, answer: Uint128 },
 }
 
 /// Message type for `migrate` entry_point
 #[cw_serde]
-pub enum MigrateMsg {}
+pub struct MigrateMsg {
+    /// the max number of rounds of v0.1 indexed by this migration, 1000 by default,
+    /// the migration is repeated with the same code until all of them are indexed
+    pub limit: Option<u32>,
+}
 
 /// Message type for `query` entry_point
 #[cw_serde]
@@ -26,14 +98,87 @@ pub enum MigrateMsg {}
 pub enum QueryMsg {
     #[returns(RoundDataResponse)]
     LastestRoundData {},
+    /// the latest round data, fails if it is older than `max_age` seconds (or the heartbeat if not set)
+    #[returns(RoundDataResponse)]
+    LatestFreshRoundData { max_age: Option<u64> },
     #[returns(RoundDataResponse)]
     RoundData { round_id: u64 },
+    /// a page of the rounds in the history, in ascending order of round id by default
+    #[returns(Vec<RoundDataResponse>)]
+    Rounds {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<OrderBy>,
+    },
+    /// the latest round saved at or before the block height, none if there is no such round
+    #[returns(Option<RoundDataResponse>)]
+    RoundAtHeight { height: u64 },
+    /// the latest round saved at or before the time, none if there is no such round
+    #[returns(Option<RoundDataResponse>)]
+    RoundAtTime { time: Timestamp },
     #[returns(u8)]
     Decimals {},
     #[returns(String)]
     Description {},
-    #[returns(String)]
-    Controller {},
+    /// the owner and the addresses granted each role
+    #[returns(RolesResponse)]
+    Roles {},
+    /// the reason and the sender of a forced round, none if the round is not forced
+    #[returns(Option<ManualRound>)]
+    ManualRound { round_id: u64 },
+    #[returns(Option<u64>)]
+    Heartbeat {},
+    #[returns(RetentionPolicy)]
+    RetentionPolicy {},
+    #[returns(CircuitBreaker)]
+    CircuitBreaker {},
+    /// the answers are held back and the consumers should halt while the circuit is open
+    #[returns(bool)]
+    CircuitOpen {},
+    #[returns(Option<QuarantinedAnswer>)]
+    QuarantinedAnswer {},
+    #[returns(Vec<SubscriberResponse>)]
+    Subscribers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// time-weighted average of the answers of the rounds between `start_round` and `end_round`,
+    /// the range cannot include more than 500 rounds
+    #[returns(TwapResponse)]
+    Twap { start_round: u64, end_round: u64 },
+    /// time-weighted average of the answers in the last `seconds` seconds,
+    /// the window cannot include more than 500 rounds
+    #[returns(TwapResponse)]
+    TwapWindow { seconds: u64 },
+    /// the latest round in the AggregatorV3 format, also accepted as `latestRoundData`
+    #[returns(AggregatorRoundDataResponse)]
+    #[serde(alias = "latestRoundData")]
+    LatestRoundData {},
+    /// the round with exactly this id in the AggregatorV3 format, also accepted as `getRoundData`
+    #[returns(AggregatorRoundDataResponse)]
+    #[serde(alias = "getRoundData")]
+    GetRoundData { round_id: u64 },
+    /// the version of the AggregatorV3 interface implemented by the price feed
+    #[returns(u64)]
+    Version {},
+    #[returns(Ownership)]
+    Ownership {},
+}
+
+// the order of the rounds returned by the Rounds query
+#[cw_serde]
+pub enum OrderBy {
+    Ascending,
+    Descending,
+}
+
+impl From<OrderBy> for Order {
+    fn from(order: OrderBy) -> Self {
+        match order {
+            OrderBy::Ascending => Order::Ascending,
+            OrderBy::Descending => Order::Descending,
+        }
+    }
 }
 
 // the data struct of each round
@@ -44,3 +189,46 @@ pub struct RoundDataResponse {
     pub updated_at: Timestamp,
     pub answered_in_round: u64,
 }
+
+impl RoundDataResponse {
+    // the answer is stale if it was updated more than `max_age` seconds before `now`
+    pub fn is_stale(&self, max_age: u64, now: Timestamp) -> bool {
+        self.updated_at.plus_seconds(max_age) < now
+    }
+}
+
+// the round data in the AggregatorV3 format, `started_at` equals `updated_at`
+// because each round is started and answered in the same transaction
+#[cw_serde]
+pub struct AggregatorRoundDataResponse {
+    pub round_id: u64,
+    pub answer: Uint128,
+    pub started_at: Timestamp,
+    pub updated_at: Timestamp,
+    pub answered_in_round: u64,
+}
+
+#[cw_serde]
+pub struct RolesResponse {
+    // none once the ownership is renounced
+    pub owner: Option<String>,
+    pub admins: Vec<String>,
+    pub writers: Vec<String>,
+    pub guardians: Vec<String>,
+}
+
+#[cw_serde]
+pub struct SubscriberResponse {
+    pub address: String,
+    pub gas_limit: u64,
+}
+
+// the time-weighted average price over a range of rounds
+#[cw_serde]
+pub struct TwapResponse {
+    pub answer: Uint128,
+    pub start_round: u64,
+    pub end_round: u64,
+    pub start_at: Timestamp,
+    pub end_at: Timestamp,
+}
@@ -1,12 +1,33 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Decimal, Empty, Timestamp, Uint128};
 use cw_storage_plus::{Item, Map};
 
 // the config of the price feed
 #[cw_serde]
 pub struct Config {
-    pub owner: Addr,
-    pub controller: Addr,
+    // the round data cannot be forced anymore once it is disabled
+    pub force_disabled: bool,
+}
+
+// the roles granted by the owner, the owner can do everything except updating the round data
+#[cw_serde]
+pub enum Role {
+    // manages the settings and the subscribers of the price feed, and can force the round data
+    Admin,
+    // updates the round data, like the price collector
+    Writer,
+    // can only force the round data in an emergency
+    Guardian,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Writer => "writer",
+            Role::Guardian => "guardian",
+        }
+    }
 }
 
 // information of the price feed
@@ -15,6 +36,8 @@ pub struct PriceFeedInfo {
     pub latest_round: u64,
     pub decimals: u8,
     pub description: String,
+    // the max number of seconds between 2 updates before the answer is considered stale
+    pub heartbeat: Option<u64>,
 }
 
 // the data struct of each round
@@ -24,9 +47,110 @@ pub struct RoundData {
     pub updated_at: Timestamp,
 }
 
+// the audit trail of a round forced by the owner or the guardian
+#[cw_serde]
+pub struct ManualRound {
+    pub forced_by: Addr,
+    pub reason: String,
+}
+
+// the retention policy of the round data, a round is pruned when any of the limits is exceeded
+#[cw_serde]
+pub struct RetentionPolicy {
+    // the max number of rounds kept in the history
+    pub max_rounds: Option<u64>,
+    // the max age of a round in seconds
+    pub max_age: Option<u64>,
+}
+
+// the circuit breaker trips when an answer is out of the bounds or jumps too far from the latest answer
+#[cw_serde]
+#[derive(Default)]
+pub struct CircuitBreaker {
+    // the max relative deviation of an answer from the latest answer
+    pub max_deviation: Option<Decimal>,
+    pub min_answer: Option<Uint128>,
+    pub max_answer: Option<Uint128>,
+}
+
+impl CircuitBreaker {
+    pub fn is_valid(&self) -> bool {
+        match (self.min_answer, self.max_answer) {
+            (Some(min_answer), Some(max_answer)) => min_answer <= max_answer,
+            _ => true,
+        }
+    }
+
+    pub fn trips(&self, answer: Uint128, latest_answer: Option<Uint128>) -> bool {
+        if self
+            .min_answer
+            .map_or(false, |min_answer| answer < min_answer)
+            || self
+                .max_answer
+                .map_or(false, |max_answer| answer > max_answer)
+        {
+            return true;
+        }
+        match (self.max_deviation, latest_answer) {
+            (Some(max_deviation), Some(latest_answer)) if !latest_answer.is_zero() => {
+                let diff = if answer > latest_answer {
+                    answer - latest_answer
+                } else {
+                    latest_answer - answer
+                };
+                Decimal::from_ratio(diff, latest_answer) > max_deviation
+            }
+            _ => false,
+        }
+    }
+}
+
+// the answer held back while the circuit breaker is open
+#[cw_serde]
+pub struct QuarantinedAnswer {
+    pub answer: Uint128,
+    pub updated_at: Timestamp,
+}
+
+// the subscription of a consumer contract to the new rounds
+#[cw_serde]
+pub struct Subscription {
+    // the max gas the callback of the subscriber can use
+    pub gas_limit: u64,
+}
+
+// the progress of indexing the rounds of v0.1, which is done in batches by the migrations
+#[cw_serde]
+pub struct RoundIndexBackfill {
+    // the last round which is indexed
+    pub start_after: Option<u64>,
+    // the latest round of v0.1, the rounds after it are indexed when they are saved
+    pub last_round: u64,
+}
+
 // the config data
 pub const CONFIG: Item<Config> = Item::new("config");
+// the addresses granted each role, keyed by the role name and the address
+pub const ROLES: Map<(&str, &Addr), Empty> = Map::new("roles");
 // the price feed info data
 pub const PRICE_FEED_INFO: Item<PriceFeedInfo> = Item::new("price_feed_info");
-// the round data is stored in the map with the round id as the key
+// the round data is stored in the map with the sequential round id as the key
 pub const ROUND_DATA: Map<u64, RoundData> = Map::new("round_data");
+// the index from the block height to the latest round saved at that height
+pub const ROUND_IDS_BY_HEIGHT: Map<u64, u64> = Map::new("round_ids_by_height");
+// the index from the block time (in seconds) to the latest round saved at that time
+pub const ROUND_IDS_BY_TIME: Map<u64, u64> = Map::new("round_ids_by_time");
+// the rounds forced by the owner or the guardian
+pub const MANUAL_ROUNDS: Map<u64, ManualRound> = Map::new("manual_rounds");
+// the retention policy of the round data
+pub const RETENTION_POLICY: Item<RetentionPolicy> = Item::new("retention_policy");
+// the circuit breaker of the answers
+pub const CIRCUIT_BREAKER: Item<CircuitBreaker> = Item::new("circuit_breaker");
+// the latest quarantined answer, the circuit is open while it exists
+pub const QUARANTINED_ANSWER: Item<QuarantinedAnswer> = Item::new("quarantined_answer");
+// the contracts which are called back on each new round
+pub const SUBSCRIBERS: Map<Addr, Subscription> = Map::new("subscribers");
+// the number of rounds stored in the round data map
+pub const ROUND_COUNT: Item<u64> = Item::new("round_count");
+// the rounds of v0.1 which are not indexed yet, removed once all of them are indexed
+pub const ROUND_INDEX_BACKFILL: Item<RoundIndexBackfill> = Item::new("round_index_backfill");
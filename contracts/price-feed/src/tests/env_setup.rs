@@ -0,0 +1,227 @@
+#[cfg(test)]
+pub mod env {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{
+        to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+        StdResult, Storage, Uint128,
+    };
+    use cw_storage_plus::Item;
+
+    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+
+    use crate::contract::{execute, instantiate, migrate, query, reply};
+    use crate::migrations::v0_1;
+    use crate::msg::{InstantiateMsg, PriceFeedHookMsg};
+    use crate::state::{RoundData, ROUND_DATA};
+
+    pub const ADMIN: &str = "aura1000000000000000000000000000000000admin";
+    pub const CONTROLLER: &str = "aura10000000000000000000000000000controller";
+    pub const GUARDIAN: &str = "aura100000000000000000000000000000guardian";
+
+    pub struct ContractInfo {
+        pub contract_addr: String,
+    }
+
+    // create app instance
+    fn mock_app() -> App {
+        AppBuilder::new().build(|_, _, _| {})
+    }
+
+    // create price feed contract
+    pub fn price_feed_contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(execute, instantiate, query)
+            .with_reply(reply)
+            .with_migrate(migrate);
+        Box::new(contract)
+    }
+
+    // the contract name and version stored by the legacy price feed
+    #[cw_serde]
+    pub struct LegacyInstantiateMsg {
+        pub contract: String,
+        pub version: String,
+    }
+
+    // the price feed of v0.1 writes its state in the old layout with one round at the current height
+    fn legacy_instantiate(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: LegacyInstantiateMsg,
+    ) -> StdResult<Response> {
+        cw2::set_contract_version(deps.storage, msg.contract, msg.version)?;
+        v0_1::CONFIG.save(
+            deps.storage,
+            &v0_1::Config {
+                owner: info.sender,
+                controller: Addr::unchecked(CONTROLLER),
+            },
+        )?;
+        save_legacy_round_data(deps.storage, &env, 100)?;
+        Ok(Response::new())
+    }
+
+    // the price feed of v0.1 keys the rounds by the block height
+    fn save_legacy_round_data(storage: &mut dyn Storage, env: &Env, answer: u64) -> StdResult<()> {
+        v0_1::PRICE_FEED_INFO.save(
+            storage,
+            &v0_1::PriceFeedInfo {
+                latest_round: env.block.height,
+                decimals: 6,
+                description: "AURA / VND".to_string(),
+            },
+        )?;
+        ROUND_DATA.save(
+            storage,
+            env.block.height,
+            &RoundData {
+                answer: Uint128::new(answer as u128),
+                updated_at: env.block.time,
+            },
+        )
+    }
+
+    #[cw_serde]
+    pub enum LegacyExecuteMsg {
+        UpdateRoundData { answer: u64 },
+    }
+
+    fn legacy_execute(
+        deps: DepsMut,
+        env: Env,
+        _info: MessageInfo,
+        msg: LegacyExecuteMsg,
+    ) -> StdResult<Response> {
+        let LegacyExecuteMsg::UpdateRoundData { answer } = msg;
+        save_legacy_round_data(deps.storage, &env, answer)?;
+        Ok(Response::new())
+    }
+
+    // instantiate a legacy price feed which stores the given contract name and version
+    // and can be migrated by the admin, returns its address and the code id of the current price feed
+    pub fn instantiate_legacy_price_feed(
+        app: &mut App,
+        contract: &str,
+        version: &str,
+    ) -> (Addr, u64) {
+        let legacy_contract_code_id = app.store_code(Box::new(ContractWrapper::new(
+            legacy_execute,
+            legacy_instantiate,
+            query,
+        )));
+        let price_feed_contract_code_id = app.store_code(price_feed_contract_template());
+        let legacy_contract_addr = app
+            .instantiate_contract(
+                legacy_contract_code_id,
+                Addr::unchecked(ADMIN),
+                &LegacyInstantiateMsg {
+                    contract: contract.to_string(),
+                    version: version.to_string(),
+                },
+                &[],
+                "test instantiate contract",
+                Some(ADMIN.to_string()),
+            )
+            .unwrap();
+        (legacy_contract_addr, price_feed_contract_code_id)
+    }
+
+    // the last PriceUpdated callback received by the subscriber
+    const LAST_PRICE: Item<(u64, Uint128)> = Item::new("last_price");
+    // the subscriber fails on every callback if it is set
+    const FAILS: Item<bool> = Item::new("fails");
+
+    fn subscriber_instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        fails: bool,
+    ) -> StdResult<Response> {
+        FAILS.save(deps.storage, &fails)?;
+        Ok(Response::new())
+    }
+
+    fn subscriber_execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: PriceFeedHookMsg,
+    ) -> StdResult<Response> {
+        if FAILS.load(deps.storage)? {
+            return Err(StdError::generic_err("subscriber failed"));
+        }
+        let PriceFeedHookMsg::PriceUpdated { round_id, answer } = msg;
+        LAST_PRICE.save(deps.storage, &(round_id, answer))?;
+        Ok(Response::new())
+    }
+
+    fn subscriber_query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        to_binary(&LAST_PRICE.may_load(deps.storage)?)
+    }
+
+    // create a consumer contract which subscribes to the price feed
+    pub fn subscriber_contract_template() -> Box<dyn Contract<Empty>> {
+        let contract =
+            ContractWrapper::new(subscriber_execute, subscriber_instantiate, subscriber_query);
+        Box::new(contract)
+    }
+
+    // instantiate a subscriber, which fails on every callback if `fails` is set
+    pub fn instantiate_subscriber(app: &mut App, fails: bool) -> Addr {
+        let subscriber_contract_code_id = app.store_code(subscriber_contract_template());
+        app.instantiate_contract(
+            subscriber_contract_code_id,
+            Addr::unchecked(ADMIN),
+            &fails,
+            &[],
+            "test instantiate contract",
+            None,
+        )
+        .unwrap()
+    }
+
+    pub fn instantiate_contracts() -> (App, Vec<ContractInfo>) {
+        // Create a new app instance
+        let mut app = mock_app();
+        // Create a vector to store all contract info ([price feed] - [0])
+        let mut contract_info_vec: Vec<ContractInfo> = Vec::new();
+
+        // store code of all contracts to the app and get the code ids
+        let price_feed_contract_code_id = app.store_code(price_feed_contract_template());
+
+        // instantiate price feed contract
+        let price_feed_contract_addr = app
+            .instantiate_contract(
+                price_feed_contract_code_id,
+                Addr::unchecked(ADMIN),
+                &InstantiateMsg {
+                    controller: CONTROLLER.to_string(),
+                    decimals: 6,
+                    description: "AURA / VND".to_string(),
+                    heartbeat: None,
+                    retention_policy: None,
+                    circuit_breaker: None,
+                },
+                &[],
+                "test instantiate contract",
+                None,
+            )
+            .unwrap();
+
+        // add contract info to the vector
+        contract_info_vec.push(ContractInfo {
+            contract_addr: price_feed_contract_addr.to_string(),
+        });
+
+        // return the app instance and contract info vector
+        (app, contract_info_vec)
+    }
+
+    #[test]
+    fn test_instantiate_contracts() {
+        let (_app, contract_info_vec) = instantiate_contracts();
+
+        // check if all contracts are instantiated
+        assert_eq!(contract_info_vec.len(), 1);
+    }
+}
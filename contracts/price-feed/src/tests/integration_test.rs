@@ -0,0 +1,1673 @@
+#![cfg(test)]
+mod tests {
+    use crate::contract::MAX_TWAP_ROUNDS;
+    use crate::msg::{ExecuteMsg, QueryMsg, RoundDataResponse, TwapResponse};
+    use crate::state::RetentionPolicy;
+    use crate::tests::env_setup::env::{instantiate_contracts, ADMIN, CONTROLLER};
+    use cosmwasm_std::{Addr, BlockInfo, StdResult, Uint128};
+    use cw_multi_test::{App, Executor};
+
+    // the controller updates the answer, then the chain moves forward `seconds` seconds,
+    // returns the id of the updated round
+    fn update_answer_and_wait(
+        app: &mut App,
+        price_feed_contract_addr: &str,
+        answer: u64,
+        seconds: u64,
+    ) -> u64 {
+        let res = app.execute_contract(
+            Addr::unchecked(CONTROLLER),
+            Addr::unchecked(price_feed_contract_addr),
+            &ExecuteMsg::UpdateRoundData { answer },
+            &[],
+        );
+        assert!(res.is_ok());
+        let round_id = latest_round_id(app, price_feed_contract_addr);
+
+        app.set_block(BlockInfo {
+            time: app.block_info().time.plus_seconds(seconds),
+            height: app.block_info().height + seconds / 5,
+            chain_id: app.block_info().chain_id,
+        });
+        round_id
+    }
+
+    fn latest_round_id(app: &App, price_feed_contract_addr: &str) -> u64 {
+        let res: RoundDataResponse = app
+            .wrap()
+            .query_wasm_smart(price_feed_contract_addr, &QueryMsg::LastestRoundData {})
+            .unwrap();
+        res.round_id
+    }
+
+    mod twap {
+        use super::*;
+
+        #[test]
+        fn twap_weights_answers_by_time() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            // 100 stays current for 10 seconds, 200 for 30 seconds and 400 for 60 seconds
+            let first_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 100, 10);
+            let second_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 200, 30);
+            let third_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 400, 60);
+
+            // (100 * 10 + 200 * 30 + 400 * 60) / 100 = 310
+            let res: TwapResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &QueryMsg::Twap {
+                        start_round: first_round,
+                        end_round: third_round,
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(310u64));
+            assert_eq!(res.start_round, first_round);
+            assert_eq!(res.end_round, third_round);
+
+            // the last round of the range stays current until the next round: (100 * 10 + 200 * 30) / 40 = 175
+            let res: TwapResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &QueryMsg::Twap {
+                        start_round: first_round,
+                        end_round: second_round,
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(175u64));
+            assert_eq!(res.end_round, second_round);
+        }
+
+        #[test]
+        fn twap_window_clips_the_oldest_round() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            update_answer_and_wait(&mut app, price_feed_contract_addr, 100, 10);
+            let second_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 200, 30);
+            let third_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 400, 60);
+
+            // the window starts 20 seconds after the second round: (200 * 10 + 400 * 60) / 70 = 371
+            let res: TwapResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &QueryMsg::TwapWindow { seconds: 70 },
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(371u64));
+            assert_eq!(res.start_round, second_round);
+            assert_eq!(res.end_round, third_round);
+            assert_eq!(res.end_at, app.block_info().time);
+
+            // an empty window returns the latest answer
+            let res: TwapResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &QueryMsg::TwapWindow { seconds: 0 },
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(400u64));
+        }
+
+        #[test]
+        fn cannot_query_twap_without_rounds() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            let res: StdResult<TwapResponse> = app.wrap().query_wasm_smart(
+                price_feed_contract_addr,
+                &QueryMsg::TwapWindow { seconds: 3600 },
+            );
+            assert!(res.is_err());
+
+            let round_id = update_answer_and_wait(&mut app, price_feed_contract_addr, 100, 10);
+
+            // the range is reversed
+            let res: StdResult<TwapResponse> = app.wrap().query_wasm_smart(
+                price_feed_contract_addr,
+                &QueryMsg::Twap {
+                    start_round: round_id + 1,
+                    end_round: round_id,
+                },
+            );
+            assert!(res.is_err());
+
+            // there is no round in the range
+            let res: StdResult<TwapResponse> = app.wrap().query_wasm_smart(
+                price_feed_contract_addr,
+                &QueryMsg::Twap {
+                    start_round: round_id + 1,
+                    end_round: round_id + 10,
+                },
+            );
+            assert!(res.is_err());
+        }
+
+        #[test]
+        fn twap_scans_a_bounded_number_of_rounds() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            // each round stays current for 5 seconds
+            let first_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 100, 5);
+            let mut last_round = first_round;
+            for _ in 0..MAX_TWAP_ROUNDS {
+                last_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 100, 5);
+            }
+
+            // the range or the window includes one round too many
+            let res: StdResult<TwapResponse> = app.wrap().query_wasm_smart(
+                price_feed_contract_addr,
+                &QueryMsg::Twap {
+                    start_round: first_round,
+                    end_round: last_round,
+                },
+            );
+            assert!(res.is_err());
+            let res: StdResult<TwapResponse> = app.wrap().query_wasm_smart(
+                price_feed_contract_addr,
+                &QueryMsg::TwapWindow {
+                    seconds: 5 * MAX_TWAP_ROUNDS as u64 + 100,
+                },
+            );
+            assert!(res.is_err());
+
+            // the largest range and a shorter window are allowed
+            let res: TwapResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &QueryMsg::Twap {
+                        start_round: first_round + 1,
+                        end_round: last_round,
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(100u64));
+            let res: TwapResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &QueryMsg::TwapWindow {
+                        seconds: 5 * MAX_TWAP_ROUNDS as u64 - 100,
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.end_round, last_round);
+        }
+    }
+
+    mod retention {
+        use super::*;
+
+        // the first round which is still stored in the history
+        fn oldest_round(app: &App, price_feed_contract_addr: &str) -> u64 {
+            let res: TwapResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &QueryMsg::Twap {
+                        start_round: 0,
+                        end_round: u64::MAX,
+                    },
+                )
+                .unwrap();
+            res.start_round
+        }
+
+        #[test]
+        fn only_owner_can_update_retention_policy() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            // prepare the update retention policy message
+            let update_retention_policy_msg = ExecuteMsg::UpdateRetentionPolicy {
+                retention_policy: RetentionPolicy {
+                    max_rounds: Some(2),
+                    max_age: None,
+                },
+            };
+
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER),
+                Addr::unchecked(price_feed_contract_addr),
+                &update_retention_policy_msg,
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &update_retention_policy_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // keeping zero rounds is not allowed
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::UpdateRetentionPolicy {
+                    retention_policy: RetentionPolicy {
+                        max_rounds: Some(0),
+                        max_age: None,
+                    },
+                },
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res: RetentionPolicy = app
+                .wrap()
+                .query_wasm_smart(price_feed_contract_addr, &QueryMsg::RetentionPolicy {})
+                .unwrap();
+            assert_eq!(res.max_rounds, Some(2));
+        }
+
+        #[test]
+        fn update_round_data_prunes_the_oldest_rounds() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            // keep only 2 rounds
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::UpdateRetentionPolicy {
+                    retention_policy: RetentionPolicy {
+                        max_rounds: Some(2),
+                        max_age: None,
+                    },
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let first_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 100, 10);
+            update_answer_and_wait(&mut app, price_feed_contract_addr, 200, 10);
+            assert_eq!(oldest_round(&app, price_feed_contract_addr), first_round);
+
+            let third_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 300, 10);
+            let fourth_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 400, 10);
+            assert_eq!(oldest_round(&app, price_feed_contract_addr), third_round);
+
+            // the history still ends at the latest round
+            let res: TwapResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &QueryMsg::Twap {
+                        start_round: 0,
+                        end_round: fourth_round,
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.end_round, fourth_round);
+        }
+
+        #[test]
+        fn owner_can_prune_history_in_batches() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            let mut rounds = vec![];
+            for answer in 1..=5 {
+                rounds.push(update_answer_and_wait(
+                    &mut app,
+                    price_feed_contract_addr,
+                    answer,
+                    100,
+                ));
+            }
+
+            // the rounds older than 250 seconds are out of the new policy
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::UpdateRetentionPolicy {
+                    retention_policy: RetentionPolicy {
+                        max_rounds: None,
+                        max_age: Some(250),
+                    },
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let prune_history_msg = ExecuteMsg::PruneHistory { limit: 2 };
+
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER),
+                Addr::unchecked(price_feed_contract_addr),
+                &prune_history_msg,
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &prune_history_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+            assert_eq!(oldest_round(&app, price_feed_contract_addr), rounds[2]);
+
+            // only one more round is too old, so the next batch stops there
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &prune_history_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+            assert_eq!(oldest_round(&app, price_feed_contract_addr), rounds[3]);
+        }
+    }
+
+    mod heartbeat {
+        use super::*;
+
+        #[test]
+        fn only_owner_can_update_heartbeat() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            // prepare the update heartbeat message
+            let update_heartbeat_msg = ExecuteMsg::UpdateHeartbeat {
+                heartbeat: Some(60),
+            };
+
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER),
+                Addr::unchecked(price_feed_contract_addr),
+                &update_heartbeat_msg,
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &update_heartbeat_msg,
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res: Option<u64> = app
+                .wrap()
+                .query_wasm_smart(price_feed_contract_addr, &QueryMsg::Heartbeat {})
+                .unwrap();
+            assert_eq!(res, Some(60));
+        }
+
+        #[test]
+        fn latest_fresh_round_data_fails_when_stale() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::UpdateHeartbeat {
+                    heartbeat: Some(60),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // the answer is 30 seconds old
+            update_answer_and_wait(&mut app, price_feed_contract_addr, 100, 30);
+
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &QueryMsg::LatestFreshRoundData { max_age: None },
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(100u64));
+
+            // the caller can ask for a fresher answer than the heartbeat
+            let res: StdResult<RoundDataResponse> = app.wrap().query_wasm_smart(
+                price_feed_contract_addr,
+                &QueryMsg::LatestFreshRoundData { max_age: Some(10) },
+            );
+            assert!(res.is_err());
+
+            // the answer is 90 seconds old
+            app.set_block(BlockInfo {
+                time: app.block_info().time.plus_seconds(60),
+                height: app.block_info().height + 12,
+                chain_id: app.block_info().chain_id,
+            });
+
+            let res: StdResult<RoundDataResponse> = app.wrap().query_wasm_smart(
+                price_feed_contract_addr,
+                &QueryMsg::LatestFreshRoundData { max_age: None },
+            );
+            assert!(res.is_err());
+
+            // the latest round data is still available for consumers who do not care
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(price_feed_contract_addr, &QueryMsg::LastestRoundData {})
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(100u64));
+        }
+    }
+
+    mod manual {
+        use crate::state::{ManualRound, Role};
+        use crate::tests::env_setup::env::GUARDIAN;
+
+        use super::*;
+
+        fn force_round_data(
+            app: &mut App,
+            price_feed_contract_addr: &str,
+            sender: &str,
+            answer: u64,
+        ) -> bool {
+            app.execute_contract(
+                Addr::unchecked(sender),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::ForceRoundData {
+                    answer,
+                    reason: "all feeders are down".to_string(),
+                },
+                &[],
+            )
+            .is_ok()
+        }
+
+        #[test]
+        fn owner_and_guardian_can_force_round_data() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            // the guardian is not set yet
+            assert!(!force_round_data(
+                &mut app,
+                price_feed_contract_addr,
+                GUARDIAN,
+                100
+            ));
+            assert!(!force_round_data(
+                &mut app,
+                price_feed_contract_addr,
+                CONTROLLER,
+                100
+            ));
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::AddRole {
+                    role: Role::Guardian,
+                    address: GUARDIAN.to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // the reason is required
+            let res = app.execute_contract(
+                Addr::unchecked(GUARDIAN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::ForceRoundData {
+                    answer: 100,
+                    reason: " ".to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app
+                .execute_contract(
+                    Addr::unchecked(GUARDIAN),
+                    Addr::unchecked(price_feed_contract_addr),
+                    &ExecuteMsg::ForceRoundData {
+                        answer: 100,
+                        reason: "all feeders are down".to_string(),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert!(res
+                .events
+                .iter()
+                .any(|event| event.ty == "wasm-force_round_data"));
+
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(price_feed_contract_addr, &QueryMsg::LastestRoundData {})
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(100u64));
+
+            let res: Option<ManualRound> = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &QueryMsg::ManualRound {
+                        round_id: res.round_id,
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                res,
+                Some(ManualRound {
+                    forced_by: Addr::unchecked(GUARDIAN),
+                    reason: "all feeders are down".to_string(),
+                })
+            );
+
+            // the rounds updated by the controller are not manual
+            let round_id = update_answer_and_wait(&mut app, price_feed_contract_addr, 200, 60);
+            let res: Option<ManualRound> = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &QueryMsg::ManualRound { round_id },
+                )
+                .unwrap();
+            assert_eq!(res, None);
+        }
+
+        #[test]
+        fn force_round_data_can_be_disabled_permanently() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::DisableForceRoundData {},
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::DisableForceRoundData {},
+                &[],
+            );
+            assert!(res.is_ok());
+
+            assert!(!force_round_data(
+                &mut app,
+                price_feed_contract_addr,
+                ADMIN,
+                100
+            ));
+        }
+    }
+
+    mod circuit_breaker {
+        use crate::state::{CircuitBreaker, QuarantinedAnswer};
+
+        use super::*;
+
+        #[test]
+        fn answer_out_of_bounds_is_quarantined() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            // the min answer must not be greater than the max answer
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::UpdateCircuitBreaker {
+                    circuit_breaker: CircuitBreaker {
+                        max_deviation: None,
+                        min_answer: Some(Uint128::from(1000u64)),
+                        max_answer: Some(Uint128::from(10u64)),
+                    },
+                },
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::UpdateCircuitBreaker {
+                    circuit_breaker: CircuitBreaker {
+                        max_deviation: None,
+                        min_answer: Some(Uint128::from(10u64)),
+                        max_answer: Some(Uint128::from(1000u64)),
+                    },
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // the answer out of the bounds opens the circuit, and the next answers are held back too
+            update_answer_and_wait(&mut app, price_feed_contract_addr, 100, 60);
+            update_answer_and_wait(&mut app, price_feed_contract_addr, 5000, 60);
+            update_answer_and_wait(&mut app, price_feed_contract_addr, 200, 60);
+
+            let res: bool = app
+                .wrap()
+                .query_wasm_smart(price_feed_contract_addr, &QueryMsg::CircuitOpen {})
+                .unwrap();
+            assert!(res);
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(price_feed_contract_addr, &QueryMsg::LastestRoundData {})
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(100u64));
+            let res: Option<QuarantinedAnswer> = app
+                .wrap()
+                .query_wasm_smart(price_feed_contract_addr, &QueryMsg::QuarantinedAnswer {})
+                .unwrap();
+            assert_eq!(res.unwrap().answer, Uint128::from(200u64));
+
+            // the owner accepts the quarantined answer after reviewing it
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::ResetCircuitBreaker { accept: true },
+                &[],
+            );
+            assert!(res.is_err());
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::ResetCircuitBreaker { accept: true },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let res: bool = app
+                .wrap()
+                .query_wasm_smart(price_feed_contract_addr, &QueryMsg::CircuitOpen {})
+                .unwrap();
+            assert!(!res);
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(price_feed_contract_addr, &QueryMsg::LastestRoundData {})
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(200u64));
+        }
+    }
+
+    mod aggregator {
+        use crate::msg::AggregatorRoundDataResponse;
+        use cosmwasm_std::{Binary, QueryRequest, WasmQuery};
+
+        use super::*;
+
+        // query the price feed with the raw json sent by the AggregatorV3 integrations
+        fn query_raw<T: serde::de::DeserializeOwned>(
+            app: &App,
+            price_feed_contract_addr: &str,
+            msg: &str,
+        ) -> StdResult<T> {
+            app.wrap().query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: price_feed_contract_addr.to_string(),
+                msg: Binary::from(msg.as_bytes()),
+            }))
+        }
+
+        #[test]
+        fn aggregator_queries_accept_chainlink_names() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            let first_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 100, 60);
+            let second_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 200, 60);
+
+            let res: AggregatorRoundDataResponse =
+                query_raw(&app, price_feed_contract_addr, r#"{"latestRoundData":{}}"#).unwrap();
+            assert_eq!(res.round_id, second_round);
+            assert_eq!(res.answer, Uint128::from(200u64));
+            assert_eq!(res.started_at, res.updated_at);
+            assert_eq!(res.answered_in_round, second_round);
+
+            // the snake case name returns the same data
+            let latest: AggregatorRoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(price_feed_contract_addr, &QueryMsg::LatestRoundData {})
+                .unwrap();
+            assert_eq!(latest, res);
+
+            let res: AggregatorRoundDataResponse = query_raw(
+                &app,
+                price_feed_contract_addr,
+                &format!(r#"{{"getRoundData":{{"round_id":{}}}}}"#, first_round),
+            )
+            .unwrap();
+            assert_eq!(res.round_id, first_round);
+            assert_eq!(res.answer, Uint128::from(100u64));
+            assert_eq!(res.answered_in_round, first_round);
+
+            let res: u8 = query_raw(&app, price_feed_contract_addr, r#"{"decimals":{}}"#).unwrap();
+            assert_eq!(res, 6);
+            let res: StdResult<String> =
+                query_raw(&app, price_feed_contract_addr, r#"{"description":{}}"#);
+            assert!(res.is_ok());
+            let res: u64 = query_raw(&app, price_feed_contract_addr, r#"{"version":{}}"#).unwrap();
+            assert_eq!(res, 1);
+        }
+
+        #[test]
+        fn get_round_data_fails_for_unknown_round() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            let round_id = update_answer_and_wait(&mut app, price_feed_contract_addr, 100, 60);
+
+            // there is no fallback to the previous round
+            let res: StdResult<AggregatorRoundDataResponse> = app.wrap().query_wasm_smart(
+                price_feed_contract_addr,
+                &QueryMsg::GetRoundData {
+                    round_id: round_id + 1,
+                },
+            );
+            assert!(res.is_err());
+        }
+    }
+
+    mod round_index {
+        use crate::msg::OrderBy;
+        use cosmwasm_std::Timestamp;
+
+        use super::*;
+
+        fn round_at_height(app: &App, price_feed_contract_addr: &str, height: u64) -> Option<u64> {
+            let res: Option<RoundDataResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &QueryMsg::RoundAtHeight { height },
+                )
+                .unwrap();
+            res.map(|round| round.round_id)
+        }
+
+        fn round_at_time(
+            app: &App,
+            price_feed_contract_addr: &str,
+            time: Timestamp,
+        ) -> Option<u64> {
+            let res: Option<RoundDataResponse> = app
+                .wrap()
+                .query_wasm_smart(price_feed_contract_addr, &QueryMsg::RoundAtTime { time })
+                .unwrap();
+            res.map(|round| round.round_id)
+        }
+
+        #[test]
+        fn round_ids_are_sequential() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            // there is no round yet
+            let res: StdResult<RoundDataResponse> = app
+                .wrap()
+                .query_wasm_smart(price_feed_contract_addr, &QueryMsg::LastestRoundData {});
+            assert!(res.is_err());
+            let res: StdResult<RoundDataResponse> = app.wrap().query_wasm_smart(
+                price_feed_contract_addr,
+                &QueryMsg::RoundData { round_id: 0 },
+            );
+            assert!(res.is_err());
+
+            let first_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 100, 60);
+            let second_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 200, 60);
+            assert_eq!(first_round, 1);
+            assert_eq!(second_round, 2);
+
+            // a round updated twice in the same block keeps its round id
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::UpdateRoundData { answer: 300 },
+                &[],
+            );
+            assert!(res.is_ok());
+            let third_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 400, 60);
+            assert_eq!(third_round, 3);
+
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &QueryMsg::RoundData {
+                        round_id: third_round,
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::from(400u64));
+            assert_eq!(res.answered_in_round, third_round);
+        }
+
+        #[test]
+        fn round_at_height_and_time_finds_the_current_round() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            let start = app.block_info();
+            assert_eq!(round_at_height(&app, price_feed_contract_addr, 0), None);
+            assert_eq!(
+                round_at_time(&app, price_feed_contract_addr, Timestamp::from_seconds(0)),
+                None
+            );
+
+            let first_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 100, 60);
+            let second_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 200, 60);
+
+            // the rounds are found at their own block and until the next round
+            assert_eq!(
+                round_at_height(&app, price_feed_contract_addr, start.height),
+                Some(first_round)
+            );
+            assert_eq!(
+                round_at_height(&app, price_feed_contract_addr, start.height + 11),
+                Some(first_round)
+            );
+            assert_eq!(
+                round_at_height(&app, price_feed_contract_addr, start.height + 12),
+                Some(second_round)
+            );
+            assert_eq!(
+                round_at_height(&app, price_feed_contract_addr, u64::MAX),
+                Some(second_round)
+            );
+            assert_eq!(
+                round_at_height(&app, price_feed_contract_addr, start.height - 1),
+                None
+            );
+
+            assert_eq!(
+                round_at_time(&app, price_feed_contract_addr, start.time.plus_seconds(59)),
+                Some(first_round)
+            );
+            assert_eq!(
+                round_at_time(&app, price_feed_contract_addr, start.time.plus_seconds(60)),
+                Some(second_round)
+            );
+            assert_eq!(
+                round_at_time(&app, price_feed_contract_addr, start.time.minus_seconds(1)),
+                None
+            );
+        }
+
+        #[test]
+        fn pruned_rounds_are_removed_from_the_indexes() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::UpdateRetentionPolicy {
+                    retention_policy: RetentionPolicy {
+                        max_rounds: Some(2),
+                        max_age: None,
+                    },
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            let start = app.block_info();
+            update_answer_and_wait(&mut app, price_feed_contract_addr, 100, 60);
+            let second_round = update_answer_and_wait(&mut app, price_feed_contract_addr, 200, 60);
+            update_answer_and_wait(&mut app, price_feed_contract_addr, 300, 60);
+
+            // the first round is pruned, so there is no round before the second one
+            assert_eq!(
+                round_at_height(&app, price_feed_contract_addr, start.height),
+                None
+            );
+            assert_eq!(
+                round_at_time(&app, price_feed_contract_addr, start.time),
+                None
+            );
+            assert_eq!(
+                round_at_time(&app, price_feed_contract_addr, start.time.plus_seconds(60)),
+                Some(second_round)
+            );
+        }
+
+        #[test]
+        fn rounds_are_paginated() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            for answer in [100, 200, 300, 400, 500] {
+                update_answer_and_wait(&mut app, price_feed_contract_addr, answer, 60);
+            }
+
+            let rounds = |start_after: Option<u64>, limit: Option<u32>, order: Option<OrderBy>| {
+                let res: Vec<RoundDataResponse> = app
+                    .wrap()
+                    .query_wasm_smart(
+                        price_feed_contract_addr,
+                        &QueryMsg::Rounds {
+                            start_after,
+                            limit,
+                            order,
+                        },
+                    )
+                    .unwrap();
+                res.iter()
+                    .map(|round| (round.round_id, round.answer.u128()))
+                    .collect::<Vec<_>>()
+            };
+
+            // the rounds are in ascending order by default
+            assert_eq!(rounds(None, Some(2), None), vec![(1, 100), (2, 200)]);
+            assert_eq!(rounds(Some(2), Some(2), None), vec![(3, 300), (4, 400)]);
+            assert_eq!(rounds(Some(5), None, None), vec![]);
+
+            // the analytics job can walk back from the latest round
+            assert_eq!(
+                rounds(None, Some(2), Some(OrderBy::Descending)),
+                vec![(5, 500), (4, 400)]
+            );
+            assert_eq!(
+                rounds(Some(4), None, Some(OrderBy::Descending)),
+                vec![(3, 300), (2, 200), (1, 100)]
+            );
+        }
+    }
+
+    mod subscribers {
+        use crate::msg::SubscriberResponse;
+        use crate::tests::env_setup::env::instantiate_subscriber;
+        use cosmwasm_std::Empty;
+
+        use super::*;
+
+        fn last_price(app: &App, subscriber: &Addr) -> Option<(u64, Uint128)> {
+            app.wrap().query_wasm_smart(subscriber, &Empty {}).unwrap()
+        }
+
+        fn add_subscriber(app: &mut App, price_feed_contract_addr: &str, subscriber: &Addr) {
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::AddSubscriber {
+                    subscriber: subscriber.to_string(),
+                    gas_limit: 200_000,
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+        }
+
+        #[test]
+        fn only_owner_can_add_subscriber() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+            let subscriber = instantiate_subscriber(&mut app, false);
+
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::AddSubscriber {
+                    subscriber: subscriber.to_string(),
+                    gas_limit: 200_000,
+                },
+                &[],
+            );
+            assert!(res.is_err());
+
+            // the gas limit must not be zero
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::AddSubscriber {
+                    subscriber: subscriber.to_string(),
+                    gas_limit: 0,
+                },
+                &[],
+            );
+            assert!(res.is_err());
+
+            add_subscriber(&mut app, price_feed_contract_addr, &subscriber);
+
+            let res: Vec<SubscriberResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    price_feed_contract_addr,
+                    &QueryMsg::Subscribers {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                res,
+                vec![SubscriberResponse {
+                    address: subscriber.to_string(),
+                    gas_limit: 200_000,
+                }]
+            );
+
+            // the subscriber can unsubscribe itself
+            let res = app.execute_contract(
+                subscriber.clone(),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::RemoveSubscriber {
+                    subscriber: subscriber.to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            update_answer_and_wait(&mut app, price_feed_contract_addr, 100, 60);
+            assert_eq!(last_price(&app, &subscriber), None);
+        }
+
+        #[test]
+        fn subscribers_are_called_back_on_new_rounds() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+            let subscriber = instantiate_subscriber(&mut app, false);
+            let failing_subscriber = instantiate_subscriber(&mut app, true);
+            add_subscriber(&mut app, price_feed_contract_addr, &subscriber);
+            add_subscriber(&mut app, price_feed_contract_addr, &failing_subscriber);
+
+            // the failing subscriber does not revert the new round
+            let res = app
+                .execute_contract(
+                    Addr::unchecked(CONTROLLER),
+                    Addr::unchecked(price_feed_contract_addr),
+                    &ExecuteMsg::UpdateRoundData { answer: 100 },
+                    &[],
+                )
+                .unwrap();
+            assert!(res
+                .events
+                .iter()
+                .any(|event| event.ty == "wasm-price_updated_failed"));
+            let round_id = latest_round_id(&app, price_feed_contract_addr);
+            assert_eq!(
+                last_price(&app, &subscriber),
+                Some((round_id, Uint128::from(100u64)))
+            );
+
+            // the forced rounds are pushed too
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::ForceRoundData {
+                    answer: 200,
+                    reason: "all feeders are down".to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+            assert_eq!(
+                last_price(&app, &subscriber),
+                Some((round_id, Uint128::from(200u64)))
+            );
+        }
+    }
+
+    mod roles {
+        use crate::msg::RolesResponse;
+        use crate::state::Role;
+        use crate::tests::env_setup::env::GUARDIAN;
+
+        use super::*;
+
+        const ADMIN2: &str = "aura1000000000000000000000000000000000admin2";
+        const WRITER2: &str = "aura100000000000000000000000000000000writer2";
+
+        fn add_role(
+            app: &mut App,
+            price_feed_contract_addr: &str,
+            sender: &str,
+            role: Role,
+            address: &str,
+        ) -> bool {
+            app.execute_contract(
+                Addr::unchecked(sender),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::AddRole {
+                    role,
+                    address: address.to_string(),
+                },
+                &[],
+            )
+            .is_ok()
+        }
+
+        fn update_heartbeat(app: &mut App, price_feed_contract_addr: &str, sender: &str) -> bool {
+            app.execute_contract(
+                Addr::unchecked(sender),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::UpdateHeartbeat {
+                    heartbeat: Some(600),
+                },
+                &[],
+            )
+            .is_ok()
+        }
+
+        #[test]
+        fn only_owner_can_manage_roles() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            assert!(!add_role(
+                &mut app,
+                price_feed_contract_addr,
+                CONTROLLER,
+                Role::Admin,
+                ADMIN2
+            ));
+            assert!(add_role(
+                &mut app,
+                price_feed_contract_addr,
+                ADMIN,
+                Role::Admin,
+                ADMIN2
+            ));
+            // the role cannot be granted twice
+            assert!(!add_role(
+                &mut app,
+                price_feed_contract_addr,
+                ADMIN,
+                Role::Admin,
+                ADMIN2
+            ));
+            // the admins cannot grant roles
+            assert!(!add_role(
+                &mut app,
+                price_feed_contract_addr,
+                ADMIN2,
+                Role::Writer,
+                WRITER2
+            ));
+            assert!(add_role(
+                &mut app,
+                price_feed_contract_addr,
+                ADMIN,
+                Role::Writer,
+                WRITER2
+            ));
+            assert!(add_role(
+                &mut app,
+                price_feed_contract_addr,
+                ADMIN,
+                Role::Guardian,
+                GUARDIAN
+            ));
+
+            let res: RolesResponse = app
+                .wrap()
+                .query_wasm_smart(price_feed_contract_addr, &QueryMsg::Roles {})
+                .unwrap();
+            assert_eq!(
+                res,
+                RolesResponse {
+                    owner: Some(ADMIN.to_string()),
+                    admins: vec![ADMIN2.to_string()],
+                    writers: vec![WRITER2.to_string(), CONTROLLER.to_string()],
+                    guardians: vec![GUARDIAN.to_string()],
+                }
+            );
+
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::RemoveRole {
+                    role: Role::Writer,
+                    address: WRITER2.to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+
+            // the role is not granted anymore
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::RemoveRole {
+                    role: Role::Writer,
+                    address: WRITER2.to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_err());
+        }
+
+        #[test]
+        fn each_role_is_checked() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+            assert!(add_role(
+                &mut app,
+                price_feed_contract_addr,
+                ADMIN,
+                Role::Admin,
+                ADMIN2
+            ));
+            assert!(add_role(
+                &mut app,
+                price_feed_contract_addr,
+                ADMIN,
+                Role::Writer,
+                WRITER2
+            ));
+            assert!(add_role(
+                &mut app,
+                price_feed_contract_addr,
+                ADMIN,
+                Role::Guardian,
+                GUARDIAN
+            ));
+
+            // only the writers can update the round data
+            for (sender, authorized) in [
+                (CONTROLLER, true),
+                (WRITER2, true),
+                (ADMIN, false),
+                (ADMIN2, false),
+                (GUARDIAN, false),
+            ] {
+                let res = app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(price_feed_contract_addr),
+                    &ExecuteMsg::UpdateRoundData { answer: 100 },
+                    &[],
+                );
+                assert_eq!(res.is_ok(), authorized);
+            }
+
+            // only the owner and the admins can update the settings
+            for (sender, authorized) in [
+                (ADMIN, true),
+                (ADMIN2, true),
+                (CONTROLLER, false),
+                (GUARDIAN, false),
+            ] {
+                assert_eq!(
+                    update_heartbeat(&mut app, price_feed_contract_addr, sender),
+                    authorized
+                );
+            }
+
+            // the guardians can force the round data, but the writers cannot
+            for (sender, authorized) in [
+                (ADMIN, true),
+                (ADMIN2, true),
+                (GUARDIAN, true),
+                (CONTROLLER, false),
+            ] {
+                let res = app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(price_feed_contract_addr),
+                    &ExecuteMsg::ForceRoundData {
+                        answer: 100,
+                        reason: "all feeders are down".to_string(),
+                    },
+                    &[],
+                );
+                assert_eq!(res.is_ok(), authorized);
+            }
+
+            // only the owner can disable the forced rounds
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN2),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::DisableForceRoundData {},
+                &[],
+            );
+            assert!(res.is_err());
+
+            // the removed writer cannot update the round data anymore
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::RemoveRole {
+                    role: Role::Writer,
+                    address: CONTROLLER.to_string(),
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+            let res = app.execute_contract(
+                Addr::unchecked(CONTROLLER),
+                Addr::unchecked(price_feed_contract_addr),
+                &ExecuteMsg::UpdateRoundData { answer: 100 },
+                &[],
+            );
+            assert!(res.is_err());
+        }
+    }
+
+    mod ownership_transfer {
+        use crate::state::Role;
+        use ownership::Ownership;
+
+        use super::*;
+
+        const NEW_OWNER: &str = "aura100000000000000000000000000000newowner";
+
+        #[test]
+        fn owner_can_transfer_ownership() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let price_feed_contract_addr = &contracts[0].contract_addr;
+
+            // the owner proposes a new owner, who accepts the ownership
+            for (sender, msg) in [
+                (
+                    ADMIN,
+                    ExecuteMsg::ProposeNewOwner {
+                        new_owner: NEW_OWNER.to_string(),
+                        expiry: None,
+                    },
+                ),
+                (NEW_OWNER, ExecuteMsg::AcceptOwnership {}),
+            ] {
+                let res = app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(price_feed_contract_addr),
+                    &msg,
+                    &[],
+                );
+                assert!(res.is_ok());
+            }
+
+            let res: Ownership = app
+                .wrap()
+                .query_wasm_smart(price_feed_contract_addr, &QueryMsg::Ownership {})
+                .unwrap();
+            assert_eq!(res.owner, Some(Addr::unchecked(NEW_OWNER)));
+
+            // the owner-only messages follow the new owner
+            for (sender, authorized) in [(ADMIN, false), (NEW_OWNER, true)] {
+                let res = app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(price_feed_contract_addr),
+                    &ExecuteMsg::RemoveRole {
+                        role: Role::Writer,
+                        address: CONTROLLER.to_string(),
+                    },
+                    &[],
+                );
+                assert_eq!(res.is_ok(), authorized);
+            }
+        }
+    }
+
+    mod migration {
+        use crate::msg::{MigrateMsg, RolesResponse};
+        use crate::tests::env_setup::env::{instantiate_legacy_price_feed, LegacyExecuteMsg};
+        use crate::ContractError;
+        use cosmwasm_std::{Event, Timestamp};
+        use cw2::ContractVersion;
+        use ownership::Ownership;
+
+        use super::*;
+
+        fn contract_version(app: &App, price_feed_contract_addr: &Addr) -> ContractVersion {
+            cw2::query_contract_info(&app.wrap(), price_feed_contract_addr).unwrap()
+        }
+
+        #[test]
+        fn migrate_from_v0_1() {
+            // get integration test app and a price feed of v0.1
+            let (mut app, _) = instantiate_contracts();
+            let (price_feed_contract_addr, price_feed_contract_code_id) =
+                instantiate_legacy_price_feed(&mut app, "crates.io:price-feed", "0.1.0");
+            let legacy_round_id = app.block_info().height;
+
+            // only the admin of the contract can migrate it
+            assert!(app
+                .migrate_contract(
+                    Addr::unchecked(CONTROLLER),
+                    price_feed_contract_addr.clone(),
+                    &MigrateMsg { limit: None },
+                    price_feed_contract_code_id,
+                )
+                .is_err());
+            assert!(app
+                .migrate_contract(
+                    Addr::unchecked(ADMIN),
+                    price_feed_contract_addr.clone(),
+                    &MigrateMsg { limit: None },
+                    price_feed_contract_code_id,
+                )
+                .is_ok());
+            assert_eq!(
+                contract_version(&app, &price_feed_contract_addr).version,
+                env!("CARGO_PKG_VERSION")
+            );
+
+            // the owner moves to the ownership and the controller becomes a writer
+            let ownership: Ownership = app
+                .wrap()
+                .query_wasm_smart(&price_feed_contract_addr, &QueryMsg::Ownership {})
+                .unwrap();
+            assert_eq!(ownership.owner, Some(Addr::unchecked(ADMIN)));
+            let roles: RolesResponse = app
+                .wrap()
+                .query_wasm_smart(&price_feed_contract_addr, &QueryMsg::Roles {})
+                .unwrap();
+            assert_eq!(roles.writers, vec![CONTROLLER.to_string()]);
+
+            // the rounds of v0.1 are kept
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(&price_feed_contract_addr, &QueryMsg::LastestRoundData {})
+                .unwrap();
+            assert_eq!(res.round_id, legacy_round_id);
+            assert_eq!(res.answer, Uint128::new(100));
+
+            // the next round continues from the latest round of v0.1
+            app.update_block(|block| block.height += 1);
+            let round_id =
+                update_answer_and_wait(&mut app, price_feed_contract_addr.as_str(), 200, 5);
+            assert_eq!(round_id, legacy_round_id + 1);
+            let res: RoundDataResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &price_feed_contract_addr,
+                    &QueryMsg::RoundData {
+                        round_id: legacy_round_id,
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.answer, Uint128::new(100));
+
+            // migrating the current version again keeps the state
+            assert!(app
+                .migrate_contract(
+                    Addr::unchecked(ADMIN),
+                    price_feed_contract_addr.clone(),
+                    &MigrateMsg { limit: None },
+                    price_feed_contract_code_id,
+                )
+                .is_ok());
+            assert_eq!(
+                latest_round_id(&app, price_feed_contract_addr.as_str()),
+                round_id
+            );
+        }
+
+        #[test]
+        fn migrate_from_v0_1_indexes_rounds_in_batches() {
+            // get integration test app and a price feed of v0.1 with 4 rounds
+            let (mut app, _) = instantiate_contracts();
+            let (price_feed_contract_addr, price_feed_contract_code_id) =
+                instantiate_legacy_price_feed(&mut app, "crates.io:price-feed", "0.1.0");
+            let first_height = app.block_info().height;
+            let first_time = app.block_info().time;
+            for answer in [110, 120, 130] {
+                app.update_block(|block| {
+                    block.height += 2;
+                    block.time = block.time.plus_seconds(10);
+                });
+                assert!(app
+                    .execute_contract(
+                        Addr::unchecked(CONTROLLER),
+                        price_feed_contract_addr.clone(),
+                        &LegacyExecuteMsg::UpdateRoundData { answer },
+                        &[],
+                    )
+                    .is_ok());
+            }
+
+            // each migration indexes a bounded number of rounds until all of them are indexed
+            for (indexed_rounds, backfill_finished) in [("3", "false"), ("1", "true")] {
+                let res = app
+                    .migrate_contract(
+                        Addr::unchecked(ADMIN),
+                        price_feed_contract_addr.clone(),
+                        &MigrateMsg { limit: Some(3) },
+                        price_feed_contract_code_id,
+                    )
+                    .unwrap();
+                assert!(res.has_event(
+                    &Event::new("wasm")
+                        .add_attribute("indexed_rounds", indexed_rounds)
+                        .add_attribute("backfill_finished", backfill_finished)
+                ));
+            }
+
+            // the old rounds are found by their block height and time
+            for (height, round_id, answer) in [
+                (first_height, first_height, 100),
+                (first_height + 3, first_height + 2, 110),
+                (first_height + 6, first_height + 6, 130),
+            ] {
+                let res: Option<RoundDataResponse> = app
+                    .wrap()
+                    .query_wasm_smart(
+                        &price_feed_contract_addr,
+                        &QueryMsg::RoundAtHeight { height },
+                    )
+                    .unwrap();
+                let res = res.unwrap();
+                assert_eq!(res.round_id, round_id);
+                assert_eq!(res.answer, Uint128::new(answer));
+            }
+            let res: Option<RoundDataResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    &price_feed_contract_addr,
+                    &QueryMsg::RoundAtTime {
+                        time: Timestamp::from_seconds(first_time.seconds() + 25),
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.unwrap().round_id, first_height + 4);
+
+            // the old rounds are counted, so they are pruned by the retention policy
+            assert!(app
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    price_feed_contract_addr.clone(),
+                    &ExecuteMsg::UpdateRetentionPolicy {
+                        retention_policy: RetentionPolicy {
+                            max_rounds: Some(2),
+                            max_age: None,
+                        },
+                    },
+                    &[],
+                )
+                .is_ok());
+            app.update_block(|block| block.height += 1);
+            update_answer_and_wait(&mut app, price_feed_contract_addr.as_str(), 140, 5);
+            let res: Option<RoundDataResponse> = app
+                .wrap()
+                .query_wasm_smart(
+                    &price_feed_contract_addr,
+                    &QueryMsg::RoundAtHeight {
+                        height: first_height + 4,
+                    },
+                )
+                .unwrap();
+            assert_eq!(res, None);
+            let res: StdResult<RoundDataResponse> = app.wrap().query_wasm_smart(
+                &price_feed_contract_addr,
+                &QueryMsg::RoundData {
+                    round_id: first_height + 6,
+                },
+            );
+            assert_eq!(res.unwrap().answer, Uint128::new(130));
+        }
+
+        #[test]
+        fn cannot_migrate_from_newer_version_or_other_contract() {
+            // get integration test app
+            let (mut app, _) = instantiate_contracts();
+
+            // a newer version cannot be downgraded
+            let (price_feed_contract_addr, price_feed_contract_code_id) =
+                instantiate_legacy_price_feed(&mut app, "crates.io:price-feed", "9.0.0");
+            let res = app.migrate_contract(
+                Addr::unchecked(ADMIN),
+                price_feed_contract_addr,
+                &MigrateMsg { limit: None },
+                price_feed_contract_code_id,
+            );
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::CannotDowngrade {
+                    version: "9.0.0".to_string()
+                }
+                .to_string()
+            );
+
+            // another contract cannot be migrated to the price feed
+            let (other_contract_addr, price_feed_contract_code_id) =
+                instantiate_legacy_price_feed(&mut app, "crates.io:price-collector", "0.1.0");
+            let res = app.migrate_contract(
+                Addr::unchecked(ADMIN),
+                other_contract_addr,
+                &MigrateMsg { limit: None },
+                price_feed_contract_code_id,
+            );
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::InvalidMigration {
+                    contract: "crates.io:price-collector".to_string(),
+                    version: "0.1.0".to_string()
+                }
+                .to_string()
+            );
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use cosmwasm_schema::write_api;
-use cw20_base::msg::{ExecuteMsg, QueryMsg};
+use wrap_token::msg::{ExecuteMsg, QueryMsg};
 use wrap_token::state::InstantiateMsg;
 
 fn main() {
@@ -1,18 +1,28 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    BankMsg, Binary, Coin, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128,
+    to_binary, BankMsg, Binary, Coin, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response,
+    StdError, StdResult, Storage, Uint128,
 };
 
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
 
 use cw20::Cw20Coin;
 use cw20_base::contract::{execute as cw20_execute, query as cw20_query};
-use cw20_base::msg::{ExecuteMsg, QueryMsg};
+use cw20_base::msg::{ExecuteMsg as Cw20ExecuteMsg, MigrateMsg};
 use cw20_base::state::{MinterData, TokenInfo, BALANCES, TOKEN_INFO};
-use cw20_base::ContractError;
+use cw20_base::ContractError as Cw20ContractError;
+use ownership::{
+    accept_ownership, get_ownership, initialize_owner, is_owner, propose_new_owner,
+    renounce_ownership,
+};
+use semver::Version;
 
-use crate::state::{InstantiateMsg, SupportedNative, SUPPORTED_NATIVE};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, QueryMsg, WrapTokenExecuteMsg, WrapTokenQueryMsg};
+use crate::state::{
+    InstantiateMsg, Operation, SupportedNative, GUARDIAN, PAUSED_OPERATIONS, SUPPORTED_NATIVE,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw20-base";
@@ -22,10 +32,13 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    // the sender is the first owner
+    initialize_owner(deps.storage, deps.api, info.sender.as_str())?;
+
     // check valid token info
     msg.validate()?;
 
@@ -78,18 +91,82 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Burn { amount } => execute_burn(deps, env, info, amount),
-        ExecuteMsg::Mint {
+        ExecuteMsg::WrapToken(msg) => match msg {
+            WrapTokenExecuteMsg::UpdateGuardian { guardian } => {
+                execute_update_guardian(deps, env, info, guardian)
+            }
+            WrapTokenExecuteMsg::Pause { operation } => execute_pause(deps, env, info, operation),
+            WrapTokenExecuteMsg::Unpause { operation } => {
+                execute_unpause(deps, env, info, operation)
+            }
+            WrapTokenExecuteMsg::ProposeNewOwner { new_owner, expiry } => {
+                Ok(propose_new_owner(deps, &env, &info, new_owner, expiry)?)
+            }
+            WrapTokenExecuteMsg::AcceptOwnership {} => Ok(accept_ownership(deps, &env, &info)?),
+            WrapTokenExecuteMsg::RenounceOwnership {} => Ok(renounce_ownership(deps, &env, &info)?),
+        },
+        ExecuteMsg::Cw20(Cw20ExecuteMsg::Burn { amount }) => execute_burn(deps, env, info, amount),
+        ExecuteMsg::Cw20(Cw20ExecuteMsg::Mint {
             recipient,
             amount: _,
-        } => execute_mint(deps, env, info, recipient),
-        _ => cw20_execute(deps, env, info, msg),
+        }) => execute_mint(deps, env, info, recipient),
+        ExecuteMsg::Cw20(msg @ Cw20ExecuteMsg::BurnFrom { .. }) => {
+            // the burn through an allowance is paused with the burn
+            assert_not_paused(deps.storage, Operation::Burn)?;
+            Ok(cw20_execute(deps, env, info, msg)?)
+        }
+        ExecuteMsg::Cw20(msg) => Ok(cw20_execute(deps, env, info, msg)?),
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // only an older or the same version of the wrap token can be migrated
+    let previous = get_contract_version(deps.storage)?;
+    if previous.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigration {
+            contract: previous.contract,
+            version: previous.version,
+        });
+    }
+    let previous_version: Version = previous.version.parse()?;
+    if previous_version > CONTRACT_VERSION.parse::<Version>()? {
+        return Err(ContractError::CannotDowngrade {
+            version: previous.version,
+        });
+    }
+
+    // the wrap token of v0.1 has no owner, the admin of the contract becomes the owner
+    if previous_version < Version::new(0, 2, 0) {
+        let admin = deps
+            .querier
+            .query_wasm_contract_info(&env.contract.address)?
+            .admin
+            .ok_or(ContractError::Unauthorized {})?;
+        initialize_owner(deps.storage, deps.api, &admin)?;
+    }
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("previous_version", previous.version)
+        .add_attribute("version", CONTRACT_VERSION))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    cw20_query(deps, env, msg)
+    match msg {
+        QueryMsg::WrapToken(WrapTokenQueryMsg::Guardian {}) => {
+            to_binary(&GUARDIAN.may_load(deps.storage)?)
+        }
+        QueryMsg::WrapToken(WrapTokenQueryMsg::PausedOperations {}) => {
+            to_binary(&query_paused_operations(deps)?)
+        }
+        QueryMsg::WrapToken(WrapTokenQueryMsg::Ownership {}) => {
+            to_binary(&get_ownership(deps.storage)?)
+        }
+        QueryMsg::Cw20(msg) => cw20_query(deps, env, msg),
+    }
 }
 
 fn validate_balance(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
@@ -118,6 +195,9 @@ pub fn execute_burn(
     info: MessageInfo,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
+    // the burn can be paused by the guardian in an emergency
+    assert_not_paused(deps.storage, Operation::Burn)?;
+
     // lower balance of sender
     BALANCES.update(
         deps.storage,
@@ -157,6 +237,9 @@ pub fn execute_mint(
     info: MessageInfo,
     recipient: String,
 ) -> Result<Response, ContractError> {
+    // the mint can be paused by the guardian in an emergency
+    assert_not_paused(deps.storage, Operation::Mint)?;
+
     let mut config = TOKEN_INFO
         .may_load(deps.storage)?
         .ok_or(ContractError::Unauthorized {})?;
@@ -185,7 +268,7 @@ pub fn execute_mint(
     config.total_supply += info.funds[0].amount;
     if let Some(limit) = config.get_cap() {
         if config.total_supply > limit {
-            return Err(ContractError::CannotExceedCap {});
+            return Err(Cw20ContractError::CannotExceedCap {}.into());
         }
     }
     TOKEN_INFO.save(deps.storage, &config)?;
@@ -208,6 +291,92 @@ pub fn execute_mint(
         .add_attribute("amount", info.funds[0].amount))
 }
 
+pub fn execute_update_guardian(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    guardian: Option<String>,
+) -> Result<Response, ContractError> {
+    // only the owner can update the guardian
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // none removes the guardian
+    let guardian = guardian
+        .map(|guardian| deps.api.addr_validate(&guardian))
+        .transpose()?;
+    match &guardian {
+        Some(guardian) => GUARDIAN.save(deps.storage, guardian)?,
+        None => GUARDIAN.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_guardian")
+        .add_attribute(
+            "guardian",
+            guardian.map_or("none".to_string(), |guardian| guardian.to_string()),
+        ))
+}
+
+pub fn execute_pause(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    operation: Operation,
+) -> Result<Response, ContractError> {
+    // only the owner or the guardian can pause an operation
+    if !is_owner(deps.storage, &info.sender)?
+        && Some(&info.sender) != GUARDIAN.may_load(deps.storage)?.as_ref()
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if PAUSED_OPERATIONS.has(deps.storage, operation.as_str()) {
+        return Err(ContractError::AlreadyPaused {});
+    }
+    PAUSED_OPERATIONS.save(deps.storage, operation.as_str(), &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pause")
+        .add_attribute("operation", operation.as_str())
+        .add_attribute("sender", info.sender))
+}
+
+pub fn execute_unpause(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    operation: Operation,
+) -> Result<Response, ContractError> {
+    // only the owner can resume a paused operation
+    if !is_owner(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !PAUSED_OPERATIONS.has(deps.storage, operation.as_str()) {
+        return Err(ContractError::NotPaused {});
+    }
+    PAUSED_OPERATIONS.remove(deps.storage, operation.as_str());
+
+    Ok(Response::new()
+        .add_attribute("action", "unpause")
+        .add_attribute("operation", operation.as_str()))
+}
+
+fn assert_not_paused(storage: &dyn Storage, operation: Operation) -> Result<(), ContractError> {
+    if PAUSED_OPERATIONS.has(storage, operation.as_str()) {
+        return Err(ContractError::Paused {});
+    }
+    Ok(())
+}
+
+pub fn query_paused_operations(deps: Deps) -> StdResult<Vec<String>> {
+    PAUSED_OPERATIONS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
 pub fn create_accounts(
     deps: &mut DepsMut,
     accounts: &[Cw20Coin],
@@ -230,7 +399,7 @@ pub fn validate_accounts(accounts: &[Cw20Coin]) -> Result<(), ContractError> {
     addresses.dedup();
 
     if addresses.len() != accounts.len() {
-        Err(ContractError::DuplicateInitialBalanceAddresses {})
+        Err(Cw20ContractError::DuplicateInitialBalanceAddresses {}.into())
     } else {
         Ok(())
     }
@@ -0,0 +1,36 @@
+use cosmwasm_std::StdError;
+use ownership::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Cw20(#[from] cw20_base::ContractError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("{0}")]
+    SemVer(#[from] semver::Error),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Cannot migrate from {contract} {version}")]
+    InvalidMigration { contract: String, version: String },
+
+    #[error("Cannot migrate from the newer version {version}")]
+    CannotDowngrade { version: String },
+
+    #[error("Operation is paused")]
+    Paused {},
+
+    #[error("Operation is already paused")]
+    AlreadyPaused {},
+
+    #[error("Operation is not paused")]
+    NotPaused {},
+}
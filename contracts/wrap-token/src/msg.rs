@@ -0,0 +1,69 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Addr;
+use cw20_base::msg::{ExecuteMsg as Cw20ExecuteMsg, QueryMsg as Cw20QueryMsg};
+use cw_utils::Expiration;
+use ownership::Ownership;
+use schemars::schema::RootSchema;
+
+use crate::state::Operation;
+
+/// Message type for `execute` entry_point, the cw20 messages have the same signatures as in cw20-base
+#[cw_serde]
+#[serde(untagged)]
+pub enum ExecuteMsg {
+    WrapToken(WrapTokenExecuteMsg),
+    Cw20(Cw20ExecuteMsg),
+}
+
+#[cw_serde]
+pub enum WrapTokenExecuteMsg {
+    // only the owner can set the guardian, none removes the guardian
+    UpdateGuardian {
+        guardian: Option<String>,
+    },
+    // pause an operation in an emergency, only the owner or the guardian can do it
+    Pause {
+        operation: Operation,
+    },
+    // resume a paused operation, only the owner can do it
+    Unpause {
+        operation: Operation,
+    },
+    // propose a new owner, who must accept the ownership before the expiry
+    ProposeNewOwner {
+        new_owner: String,
+        expiry: Option<Expiration>,
+    },
+    AcceptOwnership {},
+    // give up the ownership permanently
+    RenounceOwnership {},
+}
+
+/// Message type for `query` entry_point, the cw20 queries have the same signatures as in cw20-base
+#[cw_serde]
+#[serde(untagged)]
+pub enum QueryMsg {
+    WrapToken(WrapTokenQueryMsg),
+    Cw20(Cw20QueryMsg),
+}
+
+impl QueryResponses for QueryMsg {
+    fn response_schemas_impl() -> BTreeMap<String, RootSchema> {
+        let mut response_schemas = Cw20QueryMsg::response_schemas_impl();
+        response_schemas.extend(WrapTokenQueryMsg::response_schemas_impl());
+        response_schemas
+    }
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum WrapTokenQueryMsg {
+    #[returns(Option<Addr>)]
+    Guardian {},
+    #[returns(Vec<String>)]
+    PausedOperations {},
+    #[returns(Ownership)]
+    Ownership {},
+}
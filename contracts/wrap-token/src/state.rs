@@ -1,8 +1,8 @@
 use cosmwasm_schema::cw_serde;
 
-use cosmwasm_std::{StdError, StdResult, Uint128};
+use cosmwasm_std::{Addr, Empty, StdError, StdResult, Uint128};
 use cw20::{Cw20Coin, MinterResponse};
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 
 /// TokenContract InstantiateMsg
 #[cw_serde]
@@ -65,7 +65,27 @@ pub struct SupportedNative {
     pub denom: String,
 }
 
+// the operations which can be paused independently
+#[cw_serde]
+pub enum Operation {
+    Mint,
+    Burn,
+}
+
+impl Operation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operation::Mint => "mint",
+            Operation::Burn => "burn",
+        }
+    }
+}
+
 pub const SUPPORTED_NATIVE: Item<SupportedNative> = Item::new("supported_native");
+// the guardian can pause the operations in an emergency, like the owner
+pub const GUARDIAN: Item<Addr> = Item::new("guardian");
+// the paused operations, keyed by the operation name
+pub const PAUSED_OPERATIONS: Map<&str, Empty> = Map::new("paused_operations");
 
 #[cfg(test)]
 mod test {
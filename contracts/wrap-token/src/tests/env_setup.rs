@@ -0,0 +1,127 @@
+#[cfg(test)]
+pub mod env {
+    use cosmwasm_std::{
+        Addr, Coin, DepsMut, Empty, Env, MessageInfo, Response, StdResult, Uint128,
+    };
+    use cw20::MinterResponse;
+
+    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+
+    use crate::contract::{execute, instantiate, migrate, query};
+    use crate::state::InstantiateMsg;
+
+    pub const ADMIN: &str = "aura1000000000000000000000000000000000admin";
+    pub const USER1: &str = "aura1000000000000000000000000000000000user1";
+    pub const GUARDIAN: &str = "aura100000000000000000000000000000guardian";
+
+    pub const NATIVE_DENOM: &str = "uaura";
+    pub const NATIVE_BALANCE: u128 = 1_000_000_000_000u128;
+
+    pub struct ContractInfo {
+        pub contract_addr: String,
+    }
+
+    // create app instance and init balance of NATIVE token for user1
+    fn mock_app() -> App {
+        AppBuilder::new().build(|router, _, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &Addr::unchecked(USER1),
+                    vec![Coin {
+                        denom: NATIVE_DENOM.to_string(),
+                        amount: Uint128::new(NATIVE_BALANCE),
+                    }],
+                )
+                .unwrap();
+        })
+    }
+
+    // create wrap token contract
+    pub fn wrap_token_contract_template() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(execute, instantiate, query).with_migrate(migrate);
+        Box::new(contract)
+    }
+
+    // the wrap token of v0.1 only stores its version, it has no owner
+    fn legacy_instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> StdResult<Response> {
+        cw2::set_contract_version(deps.storage, "crates.io:cw20-base", "0.1.1")?;
+        Ok(Response::new())
+    }
+
+    // instantiate a wrap token of v0.1 which can be migrated by the admin,
+    // returns its address and the code id of the current wrap token
+    pub fn instantiate_legacy_wrap_token(app: &mut App) -> (Addr, u64) {
+        let legacy_contract_code_id = app.store_code(Box::new(ContractWrapper::new(
+            execute,
+            legacy_instantiate,
+            query,
+        )));
+        let wrap_token_contract_code_id = app.store_code(wrap_token_contract_template());
+        let legacy_contract_addr = app
+            .instantiate_contract(
+                legacy_contract_code_id,
+                Addr::unchecked(ADMIN),
+                &Empty {},
+                &[],
+                "test instantiate contract",
+                Some(ADMIN.to_string()),
+            )
+            .unwrap();
+        (legacy_contract_addr, wrap_token_contract_code_id)
+    }
+
+    pub fn instantiate_contracts() -> (App, Vec<ContractInfo>) {
+        // Create a new app instance
+        let mut app = mock_app();
+        // Create a vector to store all contract info ([wrap token] - [0])
+        let mut contract_info_vec: Vec<ContractInfo> = Vec::new();
+
+        // store code of all contracts to the app and get the code ids
+        let wrap_token_contract_code_id = app.store_code(wrap_token_contract_template());
+
+        // instantiate wrap token contract, user1 can mint the token
+        let wrap_token_contract_addr = app
+            .instantiate_contract(
+                wrap_token_contract_code_id,
+                Addr::unchecked(ADMIN),
+                &InstantiateMsg {
+                    name: "Wrapped Aura".to_string(),
+                    symbol: "WAURA".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![],
+                    mint: Some(MinterResponse {
+                        minter: USER1.to_string(),
+                        cap: None,
+                    }),
+                    native_denom: NATIVE_DENOM.to_string(),
+                },
+                &[],
+                "test instantiate contract",
+                Some(ADMIN.to_string()),
+            )
+            .unwrap();
+
+        // add contract info to the vector
+        contract_info_vec.push(ContractInfo {
+            contract_addr: wrap_token_contract_addr.to_string(),
+        });
+
+        // return the app instance and contract info vector
+        (app, contract_info_vec)
+    }
+
+    #[test]
+    fn test_instantiate_contracts() {
+        let (_app, contract_info_vec) = instantiate_contracts();
+
+        // check if all contracts are instantiated
+        assert_eq!(contract_info_vec.len(), 1);
+    }
+}
@@ -0,0 +1,290 @@
+#![cfg(test)]
+mod tests {
+    use crate::msg::{ExecuteMsg, QueryMsg, WrapTokenExecuteMsg, WrapTokenQueryMsg};
+    use crate::state::Operation;
+    use crate::tests::env_setup::env::{
+        instantiate_contracts, instantiate_legacy_wrap_token, ADMIN, GUARDIAN, NATIVE_DENOM, USER1,
+    };
+    use crate::ContractError;
+    use cosmwasm_std::{coins, Addr, Uint128};
+    use cw20_base::msg::{ExecuteMsg as Cw20ExecuteMsg, MigrateMsg, QueryMsg as Cw20QueryMsg};
+    use cw_multi_test::{App, AppResponse, Executor};
+    use ownership::Ownership;
+
+    fn execute(
+        app: &mut App,
+        wrap_token_contract_addr: &str,
+        sender: &str,
+        msg: &WrapTokenExecuteMsg,
+    ) -> Result<AppResponse, String> {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            Addr::unchecked(wrap_token_contract_addr),
+            &ExecuteMsg::WrapToken(msg.clone()),
+            &[],
+        )
+        .map_err(|err| err.root_cause().to_string())
+    }
+
+    // user1 swaps the native coins to the wrap token
+    fn mint(
+        app: &mut App,
+        wrap_token_contract_addr: &str,
+        amount: u128,
+    ) -> Result<AppResponse, String> {
+        app.execute_contract(
+            Addr::unchecked(USER1),
+            Addr::unchecked(wrap_token_contract_addr),
+            &Cw20ExecuteMsg::Mint {
+                recipient: USER1.to_string(),
+                amount: Uint128::new(amount),
+            },
+            &coins(amount, NATIVE_DENOM),
+        )
+        .map_err(|err| err.root_cause().to_string())
+    }
+
+    // user1 swaps the wrap token back to the native coins
+    fn burn(
+        app: &mut App,
+        wrap_token_contract_addr: &str,
+        amount: u128,
+    ) -> Result<AppResponse, String> {
+        app.execute_contract(
+            Addr::unchecked(USER1),
+            Addr::unchecked(wrap_token_contract_addr),
+            &Cw20ExecuteMsg::Burn {
+                amount: Uint128::new(amount),
+            },
+            &[],
+        )
+        .map_err(|err| err.root_cause().to_string())
+    }
+
+    mod ownership_transfer {
+        use super::*;
+
+        #[test]
+        fn owner_can_transfer_ownership() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let wrap_token_contract_addr = &contracts[0].contract_addr;
+
+            // the owner proposes a new owner, who accepts the ownership
+            for (sender, msg) in [
+                (
+                    ADMIN,
+                    WrapTokenExecuteMsg::ProposeNewOwner {
+                        new_owner: USER1.to_string(),
+                        expiry: None,
+                    },
+                ),
+                (USER1, WrapTokenExecuteMsg::AcceptOwnership {}),
+            ] {
+                let res = app.execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(wrap_token_contract_addr),
+                    &ExecuteMsg::WrapToken(msg),
+                    &[],
+                );
+                assert!(res.is_ok());
+            }
+
+            let res: Ownership = app
+                .wrap()
+                .query_wasm_smart(
+                    wrap_token_contract_addr,
+                    &QueryMsg::WrapToken(WrapTokenQueryMsg::Ownership {}),
+                )
+                .unwrap();
+            assert_eq!(res.owner, Some(Addr::unchecked(USER1)));
+        }
+    }
+
+    mod pause {
+        use super::*;
+
+        #[test]
+        fn guardian_can_pause_mint_and_burn_independently() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let wrap_token_contract_addr = &contracts[0].contract_addr;
+            assert!(mint(&mut app, wrap_token_contract_addr, 100).is_ok());
+
+            // only the owner can set the guardian
+            for (sender, authorized) in [(USER1, false), (ADMIN, true)] {
+                let res = execute(
+                    &mut app,
+                    wrap_token_contract_addr,
+                    sender,
+                    &WrapTokenExecuteMsg::UpdateGuardian {
+                        guardian: Some(GUARDIAN.to_string()),
+                    },
+                );
+                assert_eq!(res.is_ok(), authorized);
+            }
+
+            // only the owner or the guardian can pause the mint
+            for (sender, authorized) in [(USER1, false), (GUARDIAN, true)] {
+                let res = execute(
+                    &mut app,
+                    wrap_token_contract_addr,
+                    sender,
+                    &WrapTokenExecuteMsg::Pause {
+                        operation: Operation::Mint,
+                    },
+                );
+                assert_eq!(res.is_ok(), authorized);
+            }
+            assert_eq!(
+                mint(&mut app, wrap_token_contract_addr, 100).unwrap_err(),
+                ContractError::Paused {}.to_string()
+            );
+
+            // the burn is not paused with the mint
+            assert!(burn(&mut app, wrap_token_contract_addr, 50).is_ok());
+            let res = execute(
+                &mut app,
+                wrap_token_contract_addr,
+                GUARDIAN,
+                &WrapTokenExecuteMsg::Pause {
+                    operation: Operation::Burn,
+                },
+            );
+            assert!(res.is_ok());
+            assert_eq!(
+                burn(&mut app, wrap_token_contract_addr, 50).unwrap_err(),
+                ContractError::Paused {}.to_string()
+            );
+
+            // the queries keep working while paused
+            let res: cw20::BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    wrap_token_contract_addr,
+                    &Cw20QueryMsg::Balance {
+                        address: USER1.to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(res.balance, Uint128::new(50));
+            let res: Vec<String> = app
+                .wrap()
+                .query_wasm_smart(
+                    wrap_token_contract_addr,
+                    &QueryMsg::WrapToken(WrapTokenQueryMsg::PausedOperations {}),
+                )
+                .unwrap();
+            assert_eq!(res, vec!["burn".to_string(), "mint".to_string()]);
+
+            // only the owner can resume the operations
+            for (sender, authorized) in [(GUARDIAN, false), (ADMIN, true)] {
+                let res = execute(
+                    &mut app,
+                    wrap_token_contract_addr,
+                    sender,
+                    &WrapTokenExecuteMsg::Unpause {
+                        operation: Operation::Mint,
+                    },
+                );
+                assert_eq!(res.is_ok(), authorized);
+            }
+            assert!(mint(&mut app, wrap_token_contract_addr, 100).is_ok());
+            assert!(burn(&mut app, wrap_token_contract_addr, 50).is_err());
+        }
+
+        #[test]
+        fn paused_burn_cannot_be_bypassed_by_allowance() {
+            // get integration test app and contracts
+            let (mut app, contracts) = instantiate_contracts();
+            let wrap_token_contract_addr = &contracts[0].contract_addr;
+            assert!(mint(&mut app, wrap_token_contract_addr, 100).is_ok());
+
+            // user1 allows the admin to burn its tokens, then the burn is paused
+            let res = app.execute_contract(
+                Addr::unchecked(USER1),
+                Addr::unchecked(wrap_token_contract_addr),
+                &Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: ADMIN.to_string(),
+                    amount: Uint128::new(100),
+                    expires: None,
+                },
+                &[],
+            );
+            assert!(res.is_ok());
+            let res = execute(
+                &mut app,
+                wrap_token_contract_addr,
+                ADMIN,
+                &WrapTokenExecuteMsg::Pause {
+                    operation: Operation::Burn,
+                },
+            );
+            assert!(res.is_ok());
+
+            // the burn through the allowance is paused with the burn
+            let burn_from = Cw20ExecuteMsg::BurnFrom {
+                owner: USER1.to_string(),
+                amount: Uint128::new(50),
+            };
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(wrap_token_contract_addr),
+                &burn_from,
+                &[],
+            );
+            assert_eq!(
+                res.unwrap_err().root_cause().to_string(),
+                ContractError::Paused {}.to_string()
+            );
+
+            // it works again once the burn is resumed
+            let res = execute(
+                &mut app,
+                wrap_token_contract_addr,
+                ADMIN,
+                &WrapTokenExecuteMsg::Unpause {
+                    operation: Operation::Burn,
+                },
+            );
+            assert!(res.is_ok());
+            let res = app.execute_contract(
+                Addr::unchecked(ADMIN),
+                Addr::unchecked(wrap_token_contract_addr),
+                &burn_from,
+                &[],
+            );
+            assert!(res.is_ok());
+        }
+    }
+
+    mod migration {
+        use super::*;
+
+        #[test]
+        fn migrate_from_v0_1_sets_admin_as_owner() {
+            // get integration test app and a wrap token of v0.1
+            let (mut app, _) = instantiate_contracts();
+            let (wrap_token_contract_addr, wrap_token_contract_code_id) =
+                instantiate_legacy_wrap_token(&mut app);
+
+            let res = app.migrate_contract(
+                Addr::unchecked(ADMIN),
+                wrap_token_contract_addr.clone(),
+                &MigrateMsg {},
+                wrap_token_contract_code_id,
+            );
+            assert!(res.is_ok());
+
+            // the admin of the contract becomes the owner
+            let res: Ownership = app
+                .wrap()
+                .query_wasm_smart(
+                    &wrap_token_contract_addr,
+                    &QueryMsg::WrapToken(WrapTokenQueryMsg::Ownership {}),
+                )
+                .unwrap();
+            assert_eq!(res.owner, Some(Addr::unchecked(ADMIN)));
+        }
+    }
+}
@@ -0,0 +1,3 @@
+#[cfg(test)]
+mod env_setup;
+mod integration_test;
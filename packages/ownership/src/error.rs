@@ -0,0 +1,23 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum OwnershipError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Ownership Renounced")]
+    NoOwner {},
+
+    #[error("No Pending Owner")]
+    NoPendingOwner {},
+
+    #[error("Ownership Transfer Expired")]
+    TransferExpired {},
+
+    #[error("Invalid Expiry")]
+    InvalidExpiry {},
+}
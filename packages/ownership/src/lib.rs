@@ -0,0 +1,288 @@
+mod error;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Api, BlockInfo, DepsMut, Env, MessageInfo, Response, StdResult, Storage};
+use cw_storage_plus::Item;
+use cw_utils::Expiration;
+
+pub use crate::error::OwnershipError;
+
+// the owner of the contract and the pending transfer of the ownership
+#[cw_serde]
+pub struct Ownership {
+    // none once the ownership is renounced
+    pub owner: Option<Addr>,
+    // the proposed owner, who must accept the ownership before it expires
+    pub pending_owner: Option<Addr>,
+    pub pending_expiry: Option<Expiration>,
+}
+
+// the ownership data
+pub const OWNERSHIP: Item<Ownership> = Item::new("ownership");
+
+// set the first owner of the contract, usually the sender of the instantiate message
+pub fn initialize_owner(storage: &mut dyn Storage, api: &dyn Api, owner: &str) -> StdResult<()> {
+    OWNERSHIP.save(
+        storage,
+        &Ownership {
+            owner: Some(api.addr_validate(owner)?),
+            pending_owner: None,
+            pending_expiry: None,
+        },
+    )
+}
+
+pub fn get_ownership(storage: &dyn Storage) -> StdResult<Ownership> {
+    OWNERSHIP.load(storage)
+}
+
+pub fn is_owner(storage: &dyn Storage, address: &Addr) -> StdResult<bool> {
+    Ok(OWNERSHIP.load(storage)?.owner.as_ref() == Some(address))
+}
+
+// check if the sender is the current owner
+pub fn assert_owner(storage: &dyn Storage, sender: &Addr) -> Result<(), OwnershipError> {
+    match OWNERSHIP.load(storage)?.owner {
+        None => Err(OwnershipError::NoOwner {}),
+        Some(owner) if owner != *sender => Err(OwnershipError::Unauthorized {}),
+        Some(_) => Ok(()),
+    }
+}
+
+// propose a new owner, which replaces the previous proposal
+pub fn propose_new_owner(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    new_owner: String,
+    expiry: Option<Expiration>,
+) -> Result<Response, OwnershipError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let new_owner = deps.api.addr_validate(&new_owner)?;
+    if is_expired(&env.block, expiry) {
+        return Err(OwnershipError::InvalidExpiry {});
+    }
+
+    let mut ownership = OWNERSHIP.load(deps.storage)?;
+    ownership.pending_owner = Some(new_owner.clone());
+    ownership.pending_expiry = expiry;
+    OWNERSHIP.save(deps.storage, &ownership)?;
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "propose_new_owner".to_string()),
+        ("new_owner", new_owner.to_string()),
+        (
+            "expiry",
+            expiry.map_or("none".to_string(), |expiry| expiry.to_string()),
+        ),
+    ]))
+}
+
+// the proposed owner accepts the ownership before the proposal expires
+pub fn accept_ownership(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+) -> Result<Response, OwnershipError> {
+    let mut ownership = OWNERSHIP.load(deps.storage)?;
+    let pending_owner = ownership
+        .pending_owner
+        .take()
+        .ok_or(OwnershipError::NoPendingOwner {})?;
+    if info.sender != pending_owner {
+        return Err(OwnershipError::Unauthorized {});
+    }
+    if is_expired(&env.block, ownership.pending_expiry.take()) {
+        return Err(OwnershipError::TransferExpired {});
+    }
+
+    ownership.owner = Some(pending_owner.clone());
+    OWNERSHIP.save(deps.storage, &ownership)?;
+
+    // return the response
+    Ok(Response::new().add_attributes([
+        ("method", "accept_ownership"),
+        ("owner", pending_owner.as_str()),
+    ]))
+}
+
+// give up the ownership permanently, the pending proposal is removed too
+pub fn renounce_ownership(
+    deps: DepsMut,
+    _env: &Env,
+    info: &MessageInfo,
+) -> Result<Response, OwnershipError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    OWNERSHIP.save(
+        deps.storage,
+        &Ownership {
+            owner: None,
+            pending_owner: None,
+            pending_expiry: None,
+        },
+    )?;
+
+    // return the response
+    Ok(Response::new().add_attribute("method", "renounce_ownership"))
+}
+
+fn is_expired(block: &BlockInfo, expiry: Option<Expiration>) -> bool {
+    expiry.map_or(false, |expiry| expiry.is_expired(block))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+    const OWNER: &str = "owner";
+    const NEW_OWNER: &str = "new_owner";
+
+    fn setup(deps: DepsMut) {
+        initialize_owner(deps.storage, deps.api, OWNER).unwrap();
+    }
+
+    #[test]
+    fn assert_owner_checks_the_sender() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+
+        assert_eq!(assert_owner(&deps.storage, &Addr::unchecked(OWNER)), Ok(()));
+        assert_eq!(
+            assert_owner(&deps.storage, &Addr::unchecked(NEW_OWNER)),
+            Err(OwnershipError::Unauthorized {})
+        );
+    }
+
+    #[test]
+    fn propose_new_owner_by_owner_only() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        setup(deps.as_mut());
+
+        // only the owner can propose a new owner
+        let err = propose_new_owner(
+            deps.as_mut(),
+            &env,
+            &mock_info(NEW_OWNER, &[]),
+            NEW_OWNER.to_string(),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err, OwnershipError::Unauthorized {});
+
+        // the expiry must be in the future
+        let err = propose_new_owner(
+            deps.as_mut(),
+            &env,
+            &mock_info(OWNER, &[]),
+            NEW_OWNER.to_string(),
+            Some(Expiration::AtHeight(env.block.height)),
+        )
+        .unwrap_err();
+        assert_eq!(err, OwnershipError::InvalidExpiry {});
+
+        let expiry = Some(Expiration::AtHeight(env.block.height + 10));
+        propose_new_owner(
+            deps.as_mut(),
+            &env,
+            &mock_info(OWNER, &[]),
+            NEW_OWNER.to_string(),
+            expiry,
+        )
+        .unwrap();
+
+        // the owner does not change until the proposal is accepted
+        assert_eq!(
+            get_ownership(&deps.storage).unwrap(),
+            Ownership {
+                owner: Some(Addr::unchecked(OWNER)),
+                pending_owner: Some(Addr::unchecked(NEW_OWNER)),
+                pending_expiry: expiry,
+            }
+        );
+    }
+
+    #[test]
+    fn accept_ownership_by_pending_owner_before_expiry() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        setup(deps.as_mut());
+
+        // there is nothing to accept without a proposal
+        let err = accept_ownership(deps.as_mut(), &env, &mock_info(NEW_OWNER, &[])).unwrap_err();
+        assert_eq!(err, OwnershipError::NoPendingOwner {});
+
+        propose_new_owner(
+            deps.as_mut(),
+            &env,
+            &mock_info(OWNER, &[]),
+            NEW_OWNER.to_string(),
+            Some(Expiration::AtHeight(env.block.height + 10)),
+        )
+        .unwrap();
+
+        // only the pending owner can accept
+        let err = accept_ownership(deps.as_mut(), &env, &mock_info(OWNER, &[])).unwrap_err();
+        assert_eq!(err, OwnershipError::Unauthorized {});
+
+        // the proposal cannot be accepted after it expires
+        env.block.height += 10;
+        let err = accept_ownership(deps.as_mut(), &env, &mock_info(NEW_OWNER, &[])).unwrap_err();
+        assert_eq!(err, OwnershipError::TransferExpired {});
+
+        env.block.height -= 1;
+        accept_ownership(deps.as_mut(), &env, &mock_info(NEW_OWNER, &[])).unwrap();
+        assert_eq!(
+            get_ownership(&deps.storage).unwrap(),
+            Ownership {
+                owner: Some(Addr::unchecked(NEW_OWNER)),
+                pending_owner: None,
+                pending_expiry: None,
+            }
+        );
+        assert!(is_owner(&deps.storage, &Addr::unchecked(NEW_OWNER)).unwrap());
+        assert!(!is_owner(&deps.storage, &Addr::unchecked(OWNER)).unwrap());
+    }
+
+    #[test]
+    fn renounce_ownership_removes_owner_and_proposal() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        setup(deps.as_mut());
+
+        propose_new_owner(
+            deps.as_mut(),
+            &env,
+            &mock_info(OWNER, &[]),
+            NEW_OWNER.to_string(),
+            None,
+        )
+        .unwrap();
+
+        // only the owner can renounce the ownership
+        let err = renounce_ownership(deps.as_mut(), &env, &mock_info(NEW_OWNER, &[])).unwrap_err();
+        assert_eq!(err, OwnershipError::Unauthorized {});
+
+        renounce_ownership(deps.as_mut(), &env, &mock_info(OWNER, &[])).unwrap();
+        assert_eq!(
+            get_ownership(&deps.storage).unwrap(),
+            Ownership {
+                owner: None,
+                pending_owner: None,
+                pending_expiry: None,
+            }
+        );
+
+        // nobody is the owner anymore, the proposal cannot be accepted either
+        assert_eq!(
+            assert_owner(&deps.storage, &Addr::unchecked(OWNER)),
+            Err(OwnershipError::NoOwner {})
+        );
+        let err = accept_ownership(deps.as_mut(), &env, &mock_info(NEW_OWNER, &[])).unwrap_err();
+        assert_eq!(err, OwnershipError::NoPendingOwner {});
+    }
+}